@@ -0,0 +1,245 @@
+use std::fmt::{self, Display, Formatter};
+
+use miniscript::bitcoin::Amount;
+use rand::Rng;
+
+use crate::traffic;
+
+pub type ActorId = u64;
+
+/// Prefix of the bitcoind wallets backing the actors.
+pub const ACTOR_WALLET_PREFIX: &str = "minta_actor_";
+
+/// Behavior of an actor, played after every block mined by the autoblock miner.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Profile {
+    /// Pays `outputs` withdrawals in one batched transaction every `blocks` blocks
+    Exchange { blocks: u32, outputs: u32 },
+    /// Pays on average `per_hour` transactions (Poisson distributed over the block
+    /// timestamps), one output each
+    Retail { per_hour: f64 },
+    /// Merges all its confirmed coins in one once it holds at least `min_utxos` of them
+    Consolidator { min_utxos: u32 },
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Profile::Exchange { blocks, outputs } => write!(
+                f,
+                "exchange, {} withdrawals every {} block(s)",
+                outputs, blocks
+            ),
+            Profile::Retail { per_hour } => write!(f, "retail, {} tx/hour", per_hour),
+            Profile::Consolidator { min_utxos } => {
+                write!(f, "consolidator, from {} coins", min_utxos)
+            }
+        }
+    }
+}
+
+/// Parameters of a new actor.
+#[derive(Debug, Clone)]
+pub struct NewActor {
+    /// The actor wallet is `ACTOR_WALLET_PREFIX` + `name`
+    pub name: String,
+    pub profile: Profile,
+    /// Range of the amount of each payment
+    pub amount_min: Amount,
+    pub amount_max: Amount,
+    /// Sent from the controlled wallet to the actor wallet at creation
+    pub funding: Amount,
+}
+
+/// What an actor does after a block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Idle,
+    /// One transaction per payment
+    Pay(u32),
+    /// One transaction w/ this count of outputs
+    Batch(u32),
+    /// Sweep the wallet to itself if it holds at least `min_utxos` confirmed coins
+    Consolidate {
+        min_utxos: u32,
+    },
+}
+
+/// An actor registered in the service.
+#[derive(Debug, Clone)]
+pub struct Actor {
+    pub id: ActorId,
+    pub name: String,
+    pub profile: Profile,
+    pub amount_min: Amount,
+    pub amount_max: Amount,
+    /// Balance of the actor wallet
+    pub balance: Amount,
+    /// Transactions sent so far
+    pub sent: u32,
+    /// Height & tip timestamp of the last action (or of the actor creation)
+    last_height: u64,
+    last_time: u64,
+}
+
+impl Actor {
+    pub fn new(id: ActorId, params: NewActor, height: u64, time: u64) -> Self {
+        Actor {
+            id,
+            name: params.name,
+            profile: params.profile,
+            amount_min: params.amount_min,
+            amount_max: params.amount_max,
+            balance: Amount::ZERO,
+            sent: 0,
+            last_height: height,
+            last_time: time,
+        }
+    }
+
+    pub fn wallet(&self) -> String {
+        format!("{}{}", ACTOR_WALLET_PREFIX, self.name)
+    }
+
+    /// What the actor does now the tip is at `height` w/ timestamp `time`.
+    pub fn act<R: Rng>(&mut self, rng: &mut R, height: u64, time: u64) -> Action {
+        // after a reorg
+        self.last_height = self.last_height.min(height);
+        match self.profile {
+            Profile::Exchange { blocks, outputs } => {
+                if height >= self.last_height + blocks.max(1) as u64 {
+                    self.last_height = height;
+                    Action::Batch(outputs.max(1))
+                } else {
+                    Action::Idle
+                }
+            }
+            Profile::Retail { per_hour } => {
+                let hours = time.saturating_sub(self.last_time) as f64 / 3600.0;
+                self.last_time = time;
+                // a long gap between blocks (or a huge rate) must not flood the node
+                let mean = (per_hour * hours).min(traffic::MAX_TX_PER_BLOCK);
+                // and no more payments than the balance can fund
+                let min = self.amount_min.min(self.amount_max).to_sat();
+                let affordable = self.balance.to_sat().checked_div(min).unwrap_or(u64::MAX);
+                match (traffic::poisson(rng, mean) as u64).min(affordable) {
+                    0 => Action::Idle,
+                    count => Action::Pay(count as u32),
+                }
+            }
+            Profile::Consolidator { min_utxos } => Action::Consolidate { min_utxos },
+        }
+    }
+}
+
+impl Display for Actor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} ({}): {}, {} tx sent",
+            self.id, self.name, self.profile, self.balance, self.sent
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn actor(profile: Profile, balance: Amount) -> Actor {
+        let params = NewActor {
+            name: "test".to_string(),
+            profile,
+            amount_min: Amount::from_sat(1_000),
+            amount_max: Amount::from_sat(2_000),
+            funding: balance,
+        };
+        let mut actor = Actor::new(0, params, 100, 1_700_000_000);
+        actor.balance = balance;
+        actor
+    }
+
+    #[test]
+    fn exchange_cadence() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let profile = Profile::Exchange {
+            blocks: 3,
+            outputs: 5,
+        };
+        let mut exchange = actor(profile, Amount::ONE_BTC);
+        let batches = (101..=110)
+            .filter(|height| exchange.act(&mut rng, *height, 0) != Action::Idle)
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![103, 106, 109]);
+        assert_eq!(exchange.act(&mut rng, 112, 0), Action::Batch(5));
+
+        // 0 blocks & 0 outputs are handled as 1
+        let profile = Profile::Exchange {
+            blocks: 0,
+            outputs: 0,
+        };
+        let mut exchange = actor(profile, Amount::ONE_BTC);
+        for height in 101..=110 {
+            assert_eq!(exchange.act(&mut rng, height, 0), Action::Batch(1));
+        }
+    }
+
+    #[test]
+    fn retail_mean() {
+        const SAMPLES: u64 = 100_000;
+        let mut rng = StdRng::seed_from_u64(42);
+        // 6 tx/hour, a block every 10 minutes
+        let mut retail = actor(Profile::Retail { per_hour: 6.0 }, Amount::ONE_BTC);
+        let mut total = 0;
+        for block in 1..=SAMPLES {
+            match retail.act(&mut rng, 100 + block, 1_700_000_000 + block * 600) {
+                Action::Idle => {}
+                Action::Pay(count) => total += count as u64,
+                action => panic!("unexpected {:?}", action),
+            }
+        }
+        let mean = total as f64 / SAMPLES as f64;
+        // Poisson(1) per block
+        traffic::assert_mean("retail", mean, 1.0, 1.0, SAMPLES as usize);
+    }
+
+    #[test]
+    fn retail_cap() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let profile = Profile::Retail { per_hour: 1e9 };
+        // a month w/o block
+        let time = 1_700_000_000 + 30 * 24 * 3600;
+        let mut retail = actor(profile.clone(), Amount::from_sat(u64::MAX / 4));
+        match retail.act(&mut rng, 101, time) {
+            Action::Pay(count) => assert!(count as f64 <= 2.0 * traffic::MAX_TX_PER_BLOCK),
+            action => panic!("unexpected {:?}", action),
+        }
+        // 10_500 sat funds 10 payments of 1_000 sat at least
+        let mut retail = actor(profile.clone(), Amount::from_sat(10_500));
+        assert_eq!(retail.act(&mut rng, 101, time), Action::Pay(10));
+        let mut retail = actor(profile, Amount::from_sat(999));
+        assert_eq!(retail.act(&mut rng, 101, time), Action::Idle);
+    }
+
+    #[test]
+    fn reorg() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let profile = Profile::Exchange {
+            blocks: 3,
+            outputs: 1,
+        };
+        let mut exchange = actor(profile, Amount::ONE_BTC);
+        assert_eq!(exchange.act(&mut rng, 103, 0), Action::Batch(1));
+        // the tip goes back to 95, the cadence restarts from there
+        assert_eq!(exchange.act(&mut rng, 95, 0), Action::Idle);
+        assert_eq!(exchange.act(&mut rng, 97, 0), Action::Idle);
+        assert_eq!(exchange.act(&mut rng, 98, 0), Action::Batch(1));
+
+        // the tip timestamp goes back, no payment until the time moves forward again
+        let mut retail = actor(Profile::Retail { per_hour: 1e6 }, Amount::ONE_BTC);
+        assert_eq!(retail.act(&mut rng, 99, 1_600_000_000), Action::Idle);
+        assert_ne!(retail.act(&mut rng, 100, 1_600_003_600), Action::Idle);
+    }
+}
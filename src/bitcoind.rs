@@ -1,23 +1,60 @@
 use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
     path::PathBuf,
     str::FromStr,
-    time::{self, Duration},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{self, Duration, Instant},
 };
 
-use bitcoincore_rpc::{jsonrpc::error::RpcError, Auth, Client, RpcApi};
+use bitcoincore_rpc::{
+    json::{self, ImportDescriptors, Timestamp},
+    Auth, Client, RpcApi,
+};
 use miniscript::{
-    bitcoin::{secp256k1::All, Address, Amount, Network, PrivateKey},
+    bitcoin::{
+        secp256k1::{All, SecretKey},
+        Address, Amount, BlockHash, CompressedPublicKey, Network, OutPoint, PrivateKey, ScriptBuf,
+        Txid,
+    },
     Descriptor, DescriptorPublicKey,
 };
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
+    actor::{Action, Actor, ActorId, NewActor},
+    descriptor::parse_descriptor,
+    error::Error,
     gui::Message::{self, Bitcoind},
     listener,
+    miner::{
+        self, BlockClock, BlockInterval, CensorCriteria, CensorRule, MiningBackend, PayoutTarget,
+        MAX_BOOTSTRAP_BLOCKS,
+    },
+    psbt::{self, AnalyzedPsbt, DecodedPsbt},
+    rawtx,
+    scheduler::{Schedule, ScheduleId, ScheduleTarget, ScheduledSend},
     service::ServiceFn,
+    signet,
+    traffic::{self, AddressMix, AddressType, JobSeed, SeedStream},
 };
 
-const WALLET_NAME: &str = "regtest";
+pub const DEFAULT_WALLET_NAME: &str = "regtest";
+const EXTERNAL_WALLET_NAME: &str = "minta_external";
+// seconds a wallet stay unlocked after walletpassphrase
+const WALLET_UNLOCK_TIMEOUT: u64 = 3600;
+// MTP is the median of the last 11 blocks, so 11 blocks stamped at the
+// mock time are always enough to reach it.
+const MAX_MTP_BLOCKS: u32 = 11;
+// node health monitoring
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const RECONNECT_MIN_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+// resolution of the time based scheduled sends
+const SCHEDULER_TICK: Duration = Duration::from_secs(1);
 
 listener!(BitcoindListener, BitcoinMessage, Message, Bitcoind);
 
@@ -66,6 +103,75 @@ pub struct SendEveryBlock {
     pub actual_index: Option<u32>,
 }
 
+#[derive(Debug, Clone)]
+pub struct AutoBlock {
+    pub interval: BlockInterval,
+    pub payout: PayoutTarget,
+    /// Mempool transactions excluded from the next blocks
+    pub censor: Vec<CensorRule>,
+}
+
+/// Balances of the controlled wallet (`getbalances`).
+#[derive(Debug, Clone, Default)]
+pub struct Balances {
+    /// Confirmed & mature, spendable
+    pub trusted: Amount,
+    /// Unconfirmed, not sent by the wallet
+    pub untrusted_pending: Amount,
+    /// Coinbase outputs w/ less than 101 confirmations
+    pub immature: Amount,
+}
+
+/// A mempool transaction, as listed by the block builder.
+#[derive(Debug, Clone)]
+pub struct MempoolTx {
+    pub txid: Txid,
+    pub vsize: u64,
+    pub fee: Amount,
+    /// Unconfirmed parents count (including itself)
+    pub ancestors: u64,
+    /// Mempool entry time
+    pub time: u64,
+    /// Unconfirmed parents
+    pub depends: Vec<Txid>,
+}
+
+impl MempoolTx {
+    /// Fee rate in sat/vB
+    pub fn fee_rate(&self) -> f64 {
+        self.fee.to_sat() as f64 / self.vsize.max(1) as f64
+    }
+}
+
+/// Mine a block containing exactly these transactions (`generateblock`).
+#[derive(Debug, Clone)]
+pub struct BuildBlock {
+    /// Mempool transactions, included in this order
+    pub txids: Vec<Txid>,
+    /// Raw hex transactions, included after the mempool ones
+    pub raw_txs: Vec<String>,
+    pub payout: PayoutTarget,
+}
+
+#[derive(Debug, Clone)]
+pub struct SendBack {
+    pub amount_min: Amount,
+    pub amount_max: Amount,
+    pub blocks: u32,
+    pub sweep: bool,
+    pub blocks_left: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateWallet {
+    pub name: String,
+    /// Descriptor wallet, legacy otherwise
+    pub descriptors: bool,
+    pub private_keys: bool,
+    /// Encrypt the wallet w/ this passphrase
+    pub passphrase: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum BitcoinMessage {
     // GUI -> Service
@@ -73,9 +179,29 @@ pub enum BitcoinMessage {
     SetCredentials {
         address: String,
         auth: AuthMethod,
+        /// Wallet controlled by Minta
+        wallet: String,
     },
     /// Connect to bitcoind
     Connect,
+    /// Request the list of loaded & available wallets
+    ListWallets,
+    CreateWallet(CreateWallet),
+    LoadWallet(String),
+    UnloadWallet(String),
+    /// Route the wallet calls to this wallet
+    SelectWallet(String),
+    UnlockWallet(String),
+    /// Set the (WIF) private key signing the blocks of a signet
+    SetSignetKey(String),
+    /// Reseed the random generator, a random seed is drawn if None, the next job
+    /// replays the given one
+    SetSeed(Option<JobSeed>),
+    /// Address types of the random addresses & controlled wallet addresses used by the
+    /// next jobs, autoblock runs and the service. A global setting is enough: each job
+    /// or autoblock run copies the mix when it starts (and reports it w/ its seed), so
+    /// changing it never alters a running one and a run is replayed from seed + mix.
+    SetAddressMix(AddressMix),
     /// Disconnect from bitcoind
     Disconnect,
 
@@ -87,10 +213,21 @@ pub enum BitcoinMessage {
     GenerateToAddress(GenerateToAddress),
     /// Generate to a descriptor
     GenerateToDescriptor(GenerateToDescriptor),
+    /// Mine to self until the wallet can spend this amount
+    Bootstrap(Amount),
     /// Generate a new receiving address
     GetNewAddress,
     /// Trigger a reorg of x blocks back
     Invalidate(u64),
+    /// Mine blocks to unknown addresses until the tip reach the given height
+    MineUntilHeight(u64),
+    /// Mine blocks to unknown addresses until the outpoint reach a confirmation count
+    MineUntilConfirmed {
+        outpoint: OutPoint,
+        confirmations: u32,
+    },
+    /// Set mocktime to the given timestamp and mine until median-time-past reach it
+    AdvanceMtp(u64),
 
     /// Send bitcoins to an address
     SendToAddress(SendToAddress),
@@ -100,123 +237,1554 @@ pub enum BitcoinMessage {
     EnableSendEveryBlock(SendEveryBlock),
     /// Disable send every block feature
     DisableSendEveryBlock,
+    /// Register a recurring payment
+    AddSchedule(Schedule),
+    RemoveSchedule(ScheduleId),
+    /// Import a private descriptor into the external wallet
+    SetExternalWallet(String),
+    /// Send bitcoins from the external wallet to an address
+    ExternalSendToAddress(SendToAddress),
+    /// Send bitcoins from the external wallet back to rpcwallet 'regtest'
+    ExternalSendToSelf(Amount),
+    /// Sweep all the external wallet funds back to rpcwallet 'regtest'
+    ExternalSweep,
+    /// Enable scheduled send back from the external wallet
+    EnableSendBack(SendBack),
+    /// Disable scheduled send back from the external wallet
+    DisableSendBack,
+    /// Create (or load) an actor wallet, fund it and register the actor
+    AddActor(NewActor),
+    /// Sweep the actor funds back to the controlled wallet and unload its wallet
+    RemoveActor(ActorId),
+    /// Decode & analyze a base64 PSBT
+    DecodePsbt(String),
+    /// Sign a PSBT with rpcwallet 'regtest'
+    SignPsbt(String),
+    /// Combine several PSBTs into one
+    CombinePsbt(Vec<String>),
+    /// Finalize a PSBT and optionally broadcast it
+    FinalizePsbt {
+        psbt: String,
+        broadcast: bool,
+    },
+    /// Decode a raw hex transaction
+    DecodeRawTx(String),
+    /// Check if a raw hex transaction would be accepted by the mempool
+    TestRawTx(String),
+    /// Broadcast a raw hex transaction, optionally mine a block containing it
+    BroadcastRawTx {
+        hex: String,
+        mine: bool,
+    },
+    /// Request the mempool transactions list
+    ListMempool,
+    /// Replace the censorship rules of the running autoblock miner
+    SetCensorRules(Vec<CensorRule>),
+    /// Mine a block w/ an explicit transaction list
+    BuildBlock(BuildBlock),
+    /// Set the node mocktime (0 disable mocktime)
+    SetMockTime(u64),
+    /// Move mocktime x seconds forward
+    AdvanceMockTime(u64),
+    /// Move mocktime x seconds forward on every autoblock block
+    SetMockTimeStep(Option<u64>),
+    /// Cancel a running job
+    CancelJob(JobId),
     /// Start auto block generation
-    StartAutoBlock(Duration),
+    StartAutoBlock(AutoBlock),
     /// Stop auto block generation
     StopAutoBlock,
 
     // Service -> GUI
     UpdateBlockchainTip(u64),
-    UpdateBalance(Amount),
-    GenerateResponse(bool),
-    SendResponse(bool),
+    UpdateBalance(Balances),
+    UpdateMedianTime(u64),
+    UpdateMockTime(Option<u64>),
+    JobUpdate(JobInfo),
+    UpdateHealth(NodeHealth),
+    UpdateNetwork(Network),
+    SignetKeySet(bool),
+    Seed(u64),
+    Wallets {
+        active: String,
+        loaded: Vec<String>,
+        available: Vec<String>,
+    },
     SendMessage(String),
     Connected(bool),
     NewAddress(String),
     IncrementSendDescriptorIndex,
     IncrementGenerateDescriptorIndex,
+    IncrementAutoblockDescriptorIndex,
+    NextBlockIn(Duration),
+    ExternalWalletLoaded(bool),
+    UpdateExternalBalance(Amount),
+    ExternalSendResponse(bool),
+    PsbtDecoded(String),
+    PsbtUpdated(String),
+    PsbtResponse(bool),
+    RawTxDecoded(String),
+    RawTxResponse(bool),
+    Mempool(Vec<MempoolTx>),
+    /// Censorship rules left after the last autoblock block
+    CensorRules(Vec<CensorRule>),
+    Schedules(Vec<ScheduledSend>),
+    Actors(Vec<Actor>),
+    BlockBuilderResponse(bool),
 
     // Loopback message from subthreads
     BlockMined,
     FailMineBlock(String),
     MinerStopped,
+    BlockCensored {
+        censored: usize,
+        rules: Vec<CensorRule>,
+    },
 
     BatchSent,
+    ScheduleTick,
+    /// Scheduled payments attempted by a round at `height`, whether each was sent
+    SchedulesSent {
+        height: u64,
+        time: Instant,
+        attempts: Vec<(ScheduleId, bool)>,
+    },
+    /// Actors played by a round
+    ActorsPlayed(Vec<Actor>),
+    JobDone(JobId),
+    NodeDown {
+        error: String,
+        retry_in: Duration,
+    },
+    NodeUp,
 }
 
-#[derive(Debug)]
-pub enum Error {
-    CredentialMissing,
-    NotConnected,
-    ParseDescriptor,
-    DeriveDescriptor,
-    Rpc(bitcoincore_rpc::Error),
+pub type JobId = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Running,
+    Done,
+    Failed(String),
+    Cancelled,
+}
+
+impl Display for JobStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatus::Running => write!(f, "running"),
+            JobStatus::Done => write!(f, "done"),
+            JobStatus::Failed(e) => write!(f, "failed: {}", e),
+            JobStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
+pub struct JobInfo {
+    pub id: JobId,
+    pub label: String,
+    pub kind: JobKind,
+    pub done: u32,
+    pub total: u32,
+    /// Last derivation index used (descriptor jobs)
+    pub index: Option<u32>,
+    /// Txid of the last transaction sent (send jobs)
+    pub last_txid: Option<Txid>,
+    /// Estimated remaining time, from the average duration of the steps already done
+    pub eta: Option<Duration>,
+    pub status: JobStatus,
+    /// Seed of the job random generator, typed back in the `jobs` panel to replay the job
+    pub seed: JobSeed,
+    /// Address types the job generates
+    pub address_mix: AddressMix,
+}
+
+/// Operations executed as jobs.
+#[derive(Debug, Clone)]
+pub enum JobKind {
+    Generate(u32),
+    GenerateToSelf(u32),
+    GenerateToAddress(GenerateToAddress),
+    GenerateToDescriptor(GenerateToDescriptor),
+    SendToAddress(SendToAddress),
+    SendToDescriptor(SendToDescriptor),
+    MineUntilHeight(u64),
+    MineUntilConfirmed {
+        outpoint: OutPoint,
+        confirmations: u32,
+    },
+    MineUntilMtp(u64),
+    /// Mine to self until the trusted balance reach this amount
+    Bootstrap(Amount),
+}
+
+impl JobKind {
+    /// Whether the job generates blocks.
+    pub fn mines(&self) -> bool {
+        !matches!(
+            self,
+            JobKind::SendToAddress(_) | JobKind::SendToDescriptor(_)
+        )
+    }
+}
+
+impl Display for JobKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JobKind::Generate(blocks) => write!(f, "Generate {} blocks to random", blocks),
+            JobKind::GenerateToSelf(blocks) => write!(f, "Generate {} blocks to self", blocks),
+            JobKind::GenerateToAddress(params) => {
+                write!(f, "Generate {} blocks to {}", params.blocks, params.address)
+            }
+            JobKind::GenerateToDescriptor(params) => write!(
+                f,
+                "Generate {} blocks to descriptor from index {}",
+                params.blocks, params.start_index
+            ),
+            JobKind::SendToAddress(params) => {
+                write!(f, "Send {} to {}", params.amount, params.address)
+            }
+            JobKind::SendToDescriptor(params) => write!(
+                f,
+                "Send {} coins to descriptor from index {}",
+                params.count, params.start_index
+            ),
+            JobKind::MineUntilHeight(height) => write!(f, "Mine until height {}", height),
+            JobKind::MineUntilConfirmed {
+                outpoint,
+                confirmations,
+            } => write!(f, "Mine until {} has {} confs", outpoint, confirmations),
+            JobKind::MineUntilMtp(timestamp) => write!(f, "Mine until MTP {}", timestamp),
+            JobKind::Bootstrap(amount) => write!(f, "Bootstrap wallet to {} spendable", amount),
+        }
+    }
+}
+
+/// A long running operation, executed on a blocking thread w/ its own RPC clients
+/// so it does not block the service loop.
+pub struct Job {
+    info: JobInfo,
+    client: Client,
+    wallet_client: Client,
+    secp: miniscript::bitcoin::secp256k1::Secp256k1<All>,
+    network: Network,
+    /// Set if the job mines blocks
+    backend: Option<MiningBackend>,
+    rng: StdRng,
+    address_mix: AddressMix,
+    sender: async_channel::Sender<BitcoinMessage>,
+    loopback: async_channel::Sender<BitcoinMessage>,
+    cancel: Arc<AtomicBool>,
+    started: Instant,
+}
+
+impl Job {
+    fn send_to_gui(&self, message: BitcoinMessage) {
+        if self.sender.try_send(message).is_err() {
+            log::debug!("Job::send_to_gui() -> Fail to send Message")
+        }
+    }
+
+    fn check_cancelled(&self) -> Result<(), Error> {
+        if self.cancel.load(Ordering::Relaxed) {
+            Err(Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn start(&mut self, total: u32) {
+        self.info.total = total;
+        self.started = Instant::now();
+        self.send_to_gui(BitcoinMessage::JobUpdate(self.info.clone()));
+    }
+
+    fn progress(&mut self, done: u32) {
+        self.info.done = done;
+        if done > 0 {
+            let per_step = self.started.elapsed() / done;
+            self.info.eta = Some(per_step * self.info.total.saturating_sub(done));
+        }
+        self.send_to_gui(BitcoinMessage::JobUpdate(self.info.clone()));
+    }
+
+    fn mine_block(&self, address: &Address) -> Result<(), Error> {
+        self.backend
+            .as_ref()
+            .ok_or(Error::MiningUnavailable(self.network))?
+            .mine(&self.client, &self.secp, address)
+    }
+
+    fn tip(&self) -> Result<u64, Error> {
+        Ok(self
+            .client
+            .get_blockchain_info()
+            .map_err(Error::from)?
+            .blocks)
+    }
+
+    /// Mine `blocks` blocks one by one, `address` returns the address the i-th block pays
+    /// to, if `start_index` is set the i-th block pays to descriptor index `start_index + i`.
+    fn mine<F>(&mut self, blocks: u32, address: F, start_index: Option<u32>) -> Result<(), Error>
+    where
+        F: Fn(&Self, u32) -> Result<Address, Error>,
+    {
+        self.start(blocks);
+        for i in 0..blocks {
+            self.check_cancelled()?;
+            let address = address(self, i)?;
+            self.mine_block(&address)?;
+            if let Some(start) = start_index {
+                self.info.index = Some(start + i);
+                self.send_to_gui(BitcoinMessage::IncrementGenerateDescriptorIndex);
+            }
+            self.progress(i + 1);
+        }
+        Ok(())
+    }
+
+    fn send_to_address(&mut self, params: &SendToAddress) -> Result<Txid, Error> {
+        BitcoinD::wallet_send(
+            &mut self.rng,
+            &self.wallet_client,
+            params,
+            &self.address_mix,
+        )
+    }
+
+    fn random_address(&mut self) -> Address {
+        BitcoinD::get_random_address(&mut self.rng, &self.secp, self.network, &self.address_mix)
+    }
+
+    fn new_wallet_address(&mut self) -> Result<Address, Error> {
+        BitcoinD::new_wallet_address(&mut self.rng, &self.wallet_client, &self.address_mix)
+    }
+
+    fn execute(&mut self, kind: JobKind) -> Result<(), Error> {
+        match kind {
+            JobKind::Generate(blocks) => {
+                let address = self.random_address();
+                self.mine(blocks, |_, _| Ok(address.clone()), None)
+            }
+            JobKind::GenerateToSelf(blocks) => {
+                let address = self.new_wallet_address()?;
+                self.mine(blocks, |_, _| Ok(address.clone()), None)
+            }
+            JobKind::GenerateToAddress(params) => {
+                self.mine(params.blocks, |_, _| Ok(params.address.clone()), None)
+            }
+            JobKind::GenerateToDescriptor(params) => {
+                let descriptor = parse_descriptor(&params.descriptor)?;
+                self.mine(
+                    params.blocks,
+                    |job, i| {
+                        BitcoinD::address_from_descriptor(
+                            &job.secp,
+                            descriptor.clone(),
+                            params.start_index + i,
+                            job.network,
+                        )
+                    },
+                    Some(params.start_index),
+                )
+            }
+            JobKind::SendToAddress(params) => {
+                self.start(1);
+                self.info.last_txid = Some(self.send_to_address(&params)?);
+                self.progress(1);
+                Ok(())
+            }
+            JobKind::SendToDescriptor(params) => {
+                let descriptor = parse_descriptor(&params.descriptor)?;
+                self.start(params.count);
+                for i in 0..params.count {
+                    self.check_cancelled()?;
+                    let amount =
+                        traffic::amount(&mut self.rng, params.amount_min, params.amount_max);
+                    let address = BitcoinD::address_from_descriptor(
+                        &self.secp,
+                        descriptor.clone(),
+                        params.start_index + i,
+                        self.network,
+                    )?;
+                    let txid = self.send_to_address(&SendToAddress { amount, address })?;
+                    self.info.index = Some(params.start_index + i);
+                    self.info.last_txid = Some(txid);
+                    self.send_to_gui(BitcoinMessage::IncrementSendDescriptorIndex);
+                    self.progress(i + 1);
+                }
+                Ok(())
+            }
+            JobKind::MineUntilHeight(height) => {
+                let blocks = u32::try_from(height.saturating_sub(self.tip()?)).map_err(|_| {
+                    Error::InvalidParameter(format!("target height {} is too far", height))
+                })?;
+                let address = self.random_address();
+                self.mine(blocks, |_, _| Ok(address.clone()), None)
+            }
+            JobKind::MineUntilConfirmed {
+                outpoint,
+                confirmations,
+            } => {
+                let utxo = self
+                    .client
+                    .get_tx_out(&outpoint.txid, outpoint.vout, Some(true))
+                    .map_err(Error::from)?
+                    .ok_or(Error::UtxoNotFound)?;
+                let blocks = confirmations.saturating_sub(utxo.confirmations);
+                let address = self.random_address();
+                self.mine(blocks, |_, _| Ok(address.clone()), None)
+            }
+            JobKind::Bootstrap(target) => {
+                let trusted = BitcoinD::balances(&self.wallet_client)?.trusted;
+                if trusted >= target {
+                    self.start(0);
+                    return Ok(());
+                }
+                let blocks = miner::bootstrap_blocks(self.tip()?, self.network, target - trusted)
+                    .ok_or_else(|| {
+                    Error::InvalidParameter(format!(
+                        "{} can't be reached in {} blocks",
+                        target, MAX_BOOTSTRAP_BLOCKS
+                    ))
+                })?;
+                let address = self.new_wallet_address()?;
+                self.start(blocks);
+                for i in 0..blocks {
+                    self.check_cancelled()?;
+                    // immature coinbases may already cover (a part of) the target
+                    if BitcoinD::balances(&self.wallet_client)?.trusted >= target {
+                        return Ok(());
+                    }
+                    self.mine_block(&address)?;
+                    self.progress(i + 1);
+                }
+                let trusted = BitcoinD::balances(&self.wallet_client)?.trusted;
+                if trusted >= target {
+                    Ok(())
+                } else {
+                    Err(Error::InsufficientFunds(format!(
+                        "only {} spendable after {} blocks",
+                        trusted, blocks
+                    )))
+                }
+            }
+            JobKind::MineUntilMtp(timestamp) => {
+                self.start(MAX_MTP_BLOCKS);
+                let address = self.random_address();
+                for i in 0..MAX_MTP_BLOCKS {
+                    self.check_cancelled()?;
+                    let info = self.client.get_blockchain_info().map_err(Error::from)?;
+                    if info.median_time >= timestamp {
+                        return Ok(());
+                    }
+                    self.mine_block(&address)?;
+                    self.progress(i + 1);
+                }
+                let info = self.client.get_blockchain_info().map_err(Error::from)?;
+                if info.median_time >= timestamp {
+                    Ok(())
+                } else {
+                    Err(Error::MtpNotReached)
+                }
+            }
+        }
+    }
+
+    pub fn run(mut self, kind: JobKind) {
+        log::info!("Job #{} start: {}", self.info.id, self.info.label);
+        self.info.status = match self.execute(kind) {
+            Ok(_) => JobStatus::Done,
+            Err(Error::Cancelled) => JobStatus::Cancelled,
+            Err(e) => {
+                self.send_to_gui(BitcoinMessage::SendMessage(e.report(&format!(
+                    "Job #{} ({}) failed",
+                    self.info.id, self.info.label
+                ))));
+                JobStatus::Failed(e.to_string())
+            }
+        };
+        log::info!("Job #{} {}", self.info.id, self.info.status);
+        self.send_to_gui(BitcoinMessage::JobUpdate(self.info.clone()));
+        if let Err(e) = self
+            .loopback
+            .try_send(BitcoinMessage::JobDone(self.info.id))
+        {
+            log::error!("Fail to snd message from job to BitcoinD: {}", e);
+        }
+    }
+}
+
+/// A round of actor payments or scheduled sends, run on a blocking thread w/ its own RPC
+/// clients like a job. The service keeps the actors & schedules, the round plays a copy
+/// and reports it back.
+struct Round {
+    client: Client,
+    wallet_client: Client,
+    secp: miniscript::bitcoin::secp256k1::Secp256k1<All>,
+    network: Network,
+    rng: StdRng,
+    address_mix: AddressMix,
+    sender: async_channel::Sender<BitcoinMessage>,
+    loopback: async_channel::Sender<BitcoinMessage>,
+}
+
+impl Round {
+    fn report(&self, error: &Error, context: &str) {
+        if self
+            .sender
+            .try_send(BitcoinMessage::SendMessage(error.report(context)))
+            .is_err()
+        {
+            log::debug!("Round::report() -> Fail to send Message")
+        }
+    }
+
+    fn send_back(&self, message: BitcoinMessage) {
+        if let Err(e) = self.loopback.try_send(message) {
+            log::error!("Fail to snd message from round to BitcoinD: {}", e);
+        }
+    }
+
+    /// Let every actor play its profile, reports the actors w/ their new state & balance.
+    fn play_actors(mut self, mut actors: Vec<(Actor, Client)>) {
+        match self.tip() {
+            Ok((height, time)) => {
+                for i in 0..actors.len() {
+                    let action = actors[i].0.act(&mut self.rng, height, time);
+                    match self.act(&actors, i, action) {
+                        Ok(sent) => actors[i].0.sent += sent,
+                        Err(e) => self.report(&e, &format!("Actor {} failed", actors[i].0.name)),
+                    }
+                }
+            }
+            Err(e) => self.report(&e, "run_actors()"),
+        }
+        for (actor, client) in &mut actors {
+            if let Ok(balance) = client.get_balance(None, None) {
+                actor.balance = balance;
+            }
+        }
+        self.send_back(BitcoinMessage::ActorsPlayed(
+            actors.into_iter().map(|(actor, _)| actor).collect(),
+        ));
+    }
+
+    fn height(&self) -> Result<u64, Error> {
+        Ok(self
+            .client
+            .get_blockchain_info()
+            .map_err(Error::from)?
+            .blocks)
+    }
+
+    /// Height & time of the tip.
+    fn tip(&self) -> Result<(u64, u64), Error> {
+        let height = self.height()?;
+        let hash = self.client.get_best_block_hash().map_err(Error::from)?;
+        let header = self.client.get_block_header(&hash).map_err(Error::from)?;
+        Ok((height, header.time as u64))
+    }
+
+    /// Address of a payment made by the actor `i`: a new address of another actor
+    /// (picked at random), or a random address if it's alone.
+    fn actor_destination(
+        &mut self,
+        actors: &[(Actor, Client)],
+        i: usize,
+    ) -> Result<Address, Error> {
+        let others = actors
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (_, client))| client)
+            .collect::<Vec<_>>();
+        if others.is_empty() {
+            Ok(BitcoinD::get_random_address(
+                &mut self.rng,
+                &self.secp,
+                self.network,
+                &self.address_mix,
+            ))
+        } else {
+            let client = others[self.rng.gen_range(0..others.len())];
+            BitcoinD::new_wallet_address(&mut self.rng, client, &self.address_mix)
+        }
+    }
+
+    /// Play `action` for the actor `i`, returns the count of transactions sent.
+    fn act(&mut self, actors: &[(Actor, Client)], i: usize, action: Action) -> Result<u32, Error> {
+        let (actor, client) = &actors[i];
+        let (min, max) = (actor.amount_min, actor.amount_max);
+        let payments = match action {
+            Action::Idle => return Ok(0),
+            Action::Pay(count) | Action::Batch(count) => (0..count)
+                .map(|_| {
+                    let address = self.actor_destination(actors, i)?;
+                    Ok((address, traffic::amount(&mut self.rng, min, max)))
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+            Action::Consolidate { .. } => Vec::new(),
+        };
+        match action {
+            Action::Idle => Ok(0),
+            Action::Pay(count) => {
+                for (address, amount) in payments {
+                    let params = SendToAddress { amount, address };
+                    BitcoinD::wallet_send(&mut self.rng, client, &params, &self.address_mix)?;
+                }
+                Ok(count)
+            }
+            Action::Batch(_) => {
+                BitcoinD::wallet_send_many(&mut self.rng, client, &payments, &self.address_mix)?;
+                Ok(1)
+            }
+            Action::Consolidate { min_utxos } => {
+                let utxos = client
+                    .list_unspent(Some(1), None, None, None, None)
+                    .map_err(Error::from)?;
+                if utxos.len() < min_utxos.max(2) as usize {
+                    return Ok(0);
+                }
+                let address =
+                    BitcoinD::new_wallet_address(&mut self.rng, client, &self.address_mix)?;
+                client
+                    .call::<serde_json::Value>(
+                        "sendall",
+                        &[
+                            serde_json::json!([address.to_string()]),
+                            serde_json::Value::Null,
+                            serde_json::Value::Null,
+                            serde_json::Value::Null,
+                            serde_json::json!({"minconf": 1}),
+                        ],
+                    )
+                    .map_err(Error::from)?;
+                Ok(1)
+            }
+        }
+    }
+
+    /// Send the due payments of `schedules`, reports the attempts.
+    fn send_schedules(mut self, schedules: Vec<ScheduledSend>) {
+        let now = Instant::now();
+        let (height, attempts) = match self.height() {
+            Ok(height) => {
+                let attempts = schedules
+                    .iter()
+                    .filter(|schedule| schedule.due(height, now))
+                    .map(|schedule| (schedule.id, self.send_schedule(schedule)))
+                    .collect();
+                (height, attempts)
+            }
+            Err(e) => {
+                log::debug!("run_schedules() -> {}", e);
+                (0, Vec::new())
+            }
+        };
+        self.send_back(BitcoinMessage::SchedulesSent {
+            height,
+            time: now,
+            attempts,
+        });
+    }
+
+    /// Send the next payment of `schedule`, returns whether it was sent.
+    fn send_schedule(&mut self, schedule: &ScheduledSend) -> bool {
+        let (min, max) = (schedule.schedule.amount_min, schedule.schedule.amount_max);
+        let amount = traffic::amount(&mut self.rng, min, max);
+        let result = self.schedule_address(schedule).and_then(|address| {
+            let params = SendToAddress { amount, address };
+            BitcoinD::wallet_send(
+                &mut self.rng,
+                &self.wallet_client,
+                &params,
+                &self.address_mix,
+            )
+        });
+        if let Err(e) = &result {
+            self.report(e, &format!("Scheduled send #{} failed", schedule.id));
+        }
+        result.is_ok()
+    }
+
+    fn schedule_address(&mut self, schedule: &ScheduledSend) -> Result<Address, Error> {
+        let index = schedule.next_index();
+        match &schedule.schedule.target {
+            ScheduleTarget::Addresses(addresses) => addresses
+                .get(index as usize % addresses.len().max(1))
+                .cloned()
+                .ok_or(Error::EmptyAddressList),
+            ScheduleTarget::Descriptor { descriptor, .. } => BitcoinD::address_from_descriptor(
+                &self.secp,
+                descriptor.clone(),
+                index,
+                self.network,
+            ),
+            ScheduleTarget::Random => Ok(BitcoinD::get_random_address(
+                &mut self.rng,
+                &self.secp,
+                self.network,
+                &self.address_mix,
+            )),
+        }
+    }
+}
+
+/// Create a RPC client, pointing to the `wallet` endpoint if any.
+fn new_client(address: &str, auth: &AuthMethod, wallet: Option<&str>) -> Result<Client, Error> {
+    let address = match wallet {
+        Some(wallet) => format!("{}/wallet/{}", address, wallet),
+        None => address.to_string(),
+    };
+    let auth = match auth {
+        AuthMethod::Cookie { cookie_path } => Auth::CookieFile(PathBuf::from(cookie_path)),
+        AuthMethod::RpcAuth { user, password } => {
+            Auth::UserPass(user.to_string(), password.to_string())
+        }
+    };
+    Client::new(&address, auth).map_err(Error::from)
+}
+
+/// Run `f` from an async task, the runtime moves its other tasks off this thread.
+fn rpc_blocking<T>(f: impl FnOnce() -> T) -> T {
+    // RPC calls are blocking
+    tokio::task::block_in_place(f)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeHealth {
+    Healthy,
+    Degraded { error: String, retry_in: Duration },
+}
+
+#[derive(Debug)]
 pub enum AutoBlockMessage {
     Stop,
+    /// The node is unreachable, skip blocks until resumed
+    Pause,
+    /// The node is back, use these fresh clients
+    Resume {
+        client: Client,
+        wallet_client: Client,
+    },
+    /// Another wallet is selected, use these clients (w/o resuming if paused)
+    UpdateClients {
+        client: Client,
+        wallet_client: Client,
+    },
+    SetCensorRules(Vec<CensorRule>),
 }
 
 pub struct BitcoinD {
     sender: async_channel::Sender<BitcoinMessage>,
-    receiver: std::sync::mpsc::Receiver<BitcoinMessage>,
-    loopback: std::sync::mpsc::Sender<BitcoinMessage>,
-    auto_block_sender: Option<std::sync::mpsc::Sender<AutoBlockMessage>>,
+    receiver: async_channel::Receiver<BitcoinMessage>,
+    loopback: async_channel::Sender<BitcoinMessage>,
+    auto_block_sender: Option<async_channel::Sender<AutoBlockMessage>>,
+    health_sender: Option<async_channel::Sender<()>>,
+    node_healthy: bool,
     client: Option<Client>,
     wallet_client: Option<Client>,
+    wallet_name: String,
+    network: Network,
+    /// Key signing the blocks on signet
+    signet_key: Option<PrivateKey>,
+    external_client: Option<Client>,
     address: Option<String>,
     auth: Option<AuthMethod>,
-    mining_busy: bool,
+    jobs: BTreeMap<JobId, Arc<AtomicBool>>,
+    next_job_id: JobId,
     secp: miniscript::bitcoin::secp256k1::Secp256k1<All>,
+    /// Seeded generator, the source of the randomness of the service (amounts, tx counts,
+    /// addresses), the jobs & miner runs derive their seed from `seed`
+    rng: StdRng,
+    seed: u64,
+    /// Jobs started since `seed` was set
+    seed_jobs: u64,
+    /// Autoblock runs since `seed` was set
+    auto_block_runs: u64,
+    address_mix: AddressMix,
     send_every_block: Option<SendEveryBlock>,
+    schedules: BTreeMap<ScheduleId, ScheduledSend>,
+    next_schedule_id: ScheduleId,
+    scheduler_sender: Option<async_channel::Sender<()>>,
+    /// A round is sending the due scheduled payments
+    sending_schedules: bool,
+    actors: BTreeMap<ActorId, Actor>,
+    /// RPC clients of the actor wallets
+    actor_clients: BTreeMap<ActorId, Client>,
+    /// A round is playing the actors
+    playing_actors: bool,
+    next_actor_id: ActorId,
+    send_back: Option<SendBack>,
+    mock_time: Option<u64>,
+    mock_time_step: Option<u64>,
 }
 
 impl BitcoinD {
     pub fn connect(&self) -> Result<(Client, Client), Error> {
+        let client = self.rpc_client(None)?;
+        log::info!("Client created!");
+        let wallet_client = self.rpc_client(Some(&self.wallet_name))?;
+        match client.load_wallet(&self.wallet_name).map_err(Error::from) {
+            Ok(_) => Ok((client, wallet_client)),
+            Err(e) => {
+                log::info!("Fail to load wallet...");
+                match e {
+                    Error::WalletNotFound(_) => {
+                        log::info!("Wallet does not exists, creating it...");
+
+                        client
+                            .create_wallet(&self.wallet_name, None, None, None, None)
+                            .map_err(Error::from)?;
+                    }
+                    Error::WalletAlreadyLoaded(_) => {
+                        log::info!("Wallet already loaded!");
+                    }
+                    e => return Err(e),
+                }
+
+                log::info!("Wallet client settxfee...");
+                wallet_client
+                    .call::<bool>("settxfee", &[0.0001.into()])
+                    .map_err(Error::from)?;
+                Ok((client, wallet_client))
+            }
+        }
+    }
+
+    /// `new_client()` w/ the credentials of the service.
+    pub fn rpc_client(&self, wallet: Option<&str>) -> Result<Client, Error> {
         if let (Some(address), Some(auth)) = (&self.address, &self.auth) {
-            let client = match auth {
-                AuthMethod::Cookie { cookie_path } => {
-                    let cookie_path = PathBuf::from(cookie_path);
-                    Client::new(address, Auth::CookieFile(cookie_path))
-                }
-                AuthMethod::RpcAuth { user, password } => Client::new(
-                    address,
-                    Auth::UserPass(user.to_string(), password.to_string()),
-                ),
-            };
-            log::info!("Client created!");
+            new_client(address, auth, wallet)
+        } else {
+            Err(Error::CredentialMissing)
+        }
+    }
 
-            let wallet_address = format!("{}/wallet/{}", address, WALLET_NAME);
-            let wallet_client = match auth {
-                AuthMethod::Cookie { cookie_path } => {
-                    let cookie_path = PathBuf::from(cookie_path);
-                    Client::new(&wallet_address, Auth::CookieFile(cookie_path))
-                }
-                AuthMethod::RpcAuth { user, password } => Client::new(
-                    &wallet_address,
-                    Auth::UserPass(user.to_string(), password.to_string()),
-                ),
+    /// Load `wallet`, it's not an error if it's already loaded.
+    fn reload_wallet(client: &Client, wallet: &str) -> Result<(), Error> {
+        match client.load_wallet(wallet).map_err(Error::from) {
+            Ok(_) | Err(Error::WalletAlreadyLoaded(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn list_wallets(&self) -> Result<(Vec<String>, Vec<String>), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        let loaded = client.list_wallets().map_err(Error::from)?;
+        // listwalletdir -> {"wallets": [{"name": ".."}, ..]}
+        let dir = client
+            .call::<serde_json::Value>("listwalletdir", &[])
+            .map_err(Error::from)?;
+        let available = dir["wallets"]
+            .as_array()
+            .map(|wallets| {
+                wallets
+                    .iter()
+                    .filter_map(|w| w["name"].as_str())
+                    .filter(|name| !loaded.iter().any(|l| l == name))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok((loaded, available))
+    }
+
+    fn update_wallets(&self) {
+        match self.list_wallets() {
+            Ok((loaded, available)) => self.send_to_gui(BitcoinMessage::Wallets {
+                active: self.wallet_name.clone(),
+                loaded,
+                available,
+            }),
+            Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                e.report("Fail to list wallets"),
+            )),
+        }
+    }
+
+    pub fn create_wallet(&self, params: &CreateWallet) -> Result<(), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        // createwallet "name" disable_private_keys blank "passphrase" avoid_reuse descriptors
+        client
+            .call::<serde_json::Value>(
+                "createwallet",
+                &[
+                    params.name.clone().into(),
+                    (!params.private_keys).into(),
+                    false.into(),
+                    params.passphrase.clone().unwrap_or_default().into(),
+                    false.into(),
+                    params.descriptors.into(),
+                ],
+            )
+            .map_err(Error::from)?;
+        Ok(())
+    }
+
+    pub fn load_wallet(&self, name: &str) -> Result<(), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        Self::reload_wallet(client, name)
+    }
+
+    pub fn unload_wallet(&self, name: &str) -> Result<(), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        if name == self.wallet_name {
+            return Err(Error::InvalidParameter(
+                "cannot unload the selected wallet".to_string(),
+            ));
+        }
+        if name == EXTERNAL_WALLET_NAME && self.external_client.is_some() {
+            return Err(Error::InvalidParameter(
+                "cannot unload the external wallet while in use".to_string(),
+            ));
+        }
+        if self.actors.values().any(|actor| actor.wallet() == name) {
+            return Err(Error::InvalidParameter(
+                "cannot unload an actor wallet, remove the actor".to_string(),
+            ));
+        }
+        client
+            .call::<serde_json::Value>("unloadwallet", &[name.into()])
+            .map_err(Error::from)?;
+        Ok(())
+    }
+
+    /// Load `name` (if not already) and route the wallet calls to it.
+    pub fn select_wallet(&mut self, name: String) -> Result<(), Error> {
+        self.load_wallet(&name)?;
+        self.wallet_client = Some(self.rpc_client(Some(&name))?);
+        self.wallet_name = name;
+        if let Some(sender) = self.auto_block_sender.as_ref() {
+            let msg = AutoBlockMessage::UpdateClients {
+                client: self.rpc_client(None)?,
+                wallet_client: self.rpc_client(Some(&self.wallet_name))?,
+            };
+            if let Err(e) = sender.try_send(msg) {
+                log::error!("Fail to snd message from BitcoinD to miner: {}", e);
             }
-            .map_err(Error::Rpc)?;
+        }
+        Ok(())
+    }
 
-            match client {
-                Ok(client) => match client.load_wallet(WALLET_NAME) {
-                    Ok(_) => Ok((client, wallet_client)),
-                    Err(e) => {
-                        log::info!("Fail to load wallet...");
-                        if let bitcoincore_rpc::Error::JsonRpc(
-                            bitcoincore_rpc::jsonrpc::Error::Rpc(RpcError { code, .. }),
-                        ) = e
-                        {
-                            // -18 => wallet does not exist
-                            if code == -18 {
-                                log::info!("Wallet does not exists, creating it...");
-
-                                client
-                                    .create_wallet(WALLET_NAME, None, None, None, None)
-                                    .map_err(Error::Rpc)?;
-                            } else if code == -35 {
-                                // -35 => wallet already loaded
-                                log::info!("Wallet already loaded!");
-                            } else {
-                                return Err(Error::Rpc(e));
-                            }
+    pub fn unlock_wallet(&self, passphrase: &str) -> Result<(), Error> {
+        let client = self.wallet_client.as_ref().ok_or(Error::NotConnected)?;
+        client
+            .call::<serde_json::Value>(
+                "walletpassphrase",
+                &[passphrase.into(), WALLET_UNLOCK_TIMEOUT.into()],
+            )
+            .map_err(Error::from)?;
+        Ok(())
+    }
 
-                            log::info!("Wallet client settxfee...");
-                            wallet_client
-                                .call::<bool>("settxfee", &[0.0001.into()])
-                                .map_err(Error::Rpc)?;
-                            Ok((client, wallet_client))
-                        } else {
-                            Err(Error::Rpc(e))
+    /// Spawn a task pinging the node every `HEALTH_CHECK_INTERVAL`, when the node is
+    /// unreachable it retries w/ an exponential backoff and notify `NodeDown`/`NodeUp`.
+    /// `NodeUp` is notified (w/ the same backoff) until the service acknowledges the
+    /// reconnection on the health channel.
+    pub fn start_health_monitor(&mut self) -> Result<(), Error> {
+        let (address, auth) = match (&self.address, &self.auth) {
+            (Some(address), Some(auth)) => (address.clone(), auth.clone()),
+            _ => return Err(Error::CredentialMissing),
+        };
+        let (sender, receiver) = async_channel::bounded::<()>(1);
+        // sending acknowledges the reconnection, dropping the sender stops the monitor
+        self.health_sender = Some(sender);
+        let loopback = self.loopback.clone();
+
+        tokio::spawn(async move {
+            let notify = |msg: BitcoinMessage| {
+                if let Err(e) = loopback.try_send(msg) {
+                    log::error!("Fail to snd message from monitor to BitcoinD: {}", e);
+                }
+            };
+            let mut delay = HEALTH_CHECK_INTERVAL;
+            let mut healthy = true;
+            loop {
+                tokio::select! {
+                    ack = receiver.recv() => match ack {
+                        Ok(()) => {
+                            healthy = true;
+                            delay = HEALTH_CHECK_INTERVAL;
+                            continue;
                         }
+                        Err(_) => break,
+                    },
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                // a new client is created on each check as the cookie changes on restart
+                let result = tokio::task::block_in_place(|| {
+                    new_client(&address, &auth, None)?
+                        .ping()
+                        .map_err(Error::from)
+                });
+                match result {
+                    Ok(_) => {
+                        delay = if healthy {
+                            HEALTH_CHECK_INTERVAL
+                        } else {
+                            // until the service reconnects
+                            log::info!("Node is back");
+                            notify(BitcoinMessage::NodeUp);
+                            (delay * 2).min(RECONNECT_MAX_DELAY)
+                        };
                     }
-                },
-                Err(e) => Err(Error::Rpc(e)),
+                    Err(e) => {
+                        delay = if healthy {
+                            RECONNECT_MIN_DELAY
+                        } else {
+                            (delay * 2).min(RECONNECT_MAX_DELAY)
+                        };
+                        healthy = false;
+                        log::warn!("Node unreachable: {}, retry in {:?}", e, delay);
+                        notify(BitcoinMessage::NodeDown {
+                            error: e.to_string(),
+                            retry_in: delay,
+                        });
+                    }
+                }
             }
+            log::info!("Health monitor stopped");
+        });
+        Ok(())
+    }
+
+    /// Recreate the RPC clients & reload the wallets after the node restarted,
+    /// then resume the miner.
+    pub fn reconnect(&mut self) -> Result<(), Error> {
+        let client = self.rpc_client(None)?;
+        Self::reload_wallet(&client, &self.wallet_name)?;
+        self.wallet_client = Some(self.rpc_client(Some(&self.wallet_name))?);
+        if self.external_client.is_some() {
+            Self::reload_wallet(&client, EXTERNAL_WALLET_NAME)?;
+            self.external_client = Some(self.rpc_client(Some(EXTERNAL_WALLET_NAME))?);
+        }
+        for actor in self.actors.values() {
+            let wallet = actor.wallet();
+            Self::reload_wallet(&client, &wallet)?;
+            self.actor_clients
+                .insert(actor.id, self.rpc_client(Some(&wallet))?);
+        }
+        self.client = Some(client);
+        if let Some(sender) = self.auto_block_sender.as_ref() {
+            let msg = AutoBlockMessage::Resume {
+                client: self.rpc_client(None)?,
+                wallet_client: self.rpc_client(Some(&self.wallet_name))?,
+            };
+            if let Err(e) = sender.try_send(msg) {
+                log::error!("Fail to snd message from BitcoinD to miner: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the external wallet (creating it blank if it does not exists) and
+    /// import the private `descriptor` into it.
+    pub fn load_external_wallet(&mut self, descriptor: &str) -> Result<(), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        match client
+            .load_wallet(EXTERNAL_WALLET_NAME)
+            .map_err(Error::from)
+        {
+            Ok(_) => {}
+            Err(Error::WalletNotFound(_)) => {
+                log::info!("External wallet does not exists, creating it...");
+                client
+                    .create_wallet(EXTERNAL_WALLET_NAME, None, Some(true), None, None)
+                    .map_err(Error::from)?;
+            }
+            Err(Error::WalletAlreadyLoaded(_)) => {
+                log::info!("External wallet already loaded!");
+            }
+            Err(e) => return Err(e),
+        }
+
+        let external = self.rpc_client(Some(EXTERNAL_WALLET_NAME))?;
+
+        // importdescriptors expect a checksum
+        let descriptor = descriptor.split('#').next().unwrap_or_default().trim();
+        let info = external
+            .get_descriptor_info(descriptor)
+            .map_err(Error::from)?;
+        if !info.has_private_keys {
+            return Err(Error::ImportDescriptor(
+                "descriptor does not contain private keys".to_string(),
+            ));
+        }
+        let checksum = info.checksum.ok_or_else(|| Error::ParseDescriptor {
+            message: "bitcoind returned no checksum".to_string(),
+            position: None,
+        })?;
+
+        let results = external
+            .import_descriptors(ImportDescriptors {
+                descriptor: format!("{}#{}", descriptor, checksum),
+                timestamp: Timestamp::Time(0),
+                active: Some(info.is_range),
+                range: None,
+                next_index: None,
+                internal: None,
+                label: None,
+            })
+            .map_err(Error::from)?;
+        for result in results {
+            if !result.success {
+                let msg = result
+                    .error
+                    .map(|e| e.message)
+                    .unwrap_or_else(|| "unknown error".to_string());
+                return Err(Error::ImportDescriptor(msg));
+            }
+        }
+
+        self.external_client = Some(external);
+        Ok(())
+    }
+
+    pub fn get_external_balance(&self) -> Result<Amount, Error> {
+        if let Some(client) = self.external_client.as_ref() {
+            client.get_balance(None, None).map_err(Error::from)
         } else {
-            Err(Error::CredentialMissing)
+            Err(Error::ExternalWalletMissing)
+        }
+    }
+
+    pub fn external_send_to_address(&self, params: SendToAddress) -> Result<(), Error> {
+        if let Some(client) = self.external_client.as_ref() {
+            client
+                .send_to_address(
+                    &params.address,
+                    params.amount,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .map_err(Error::from)?;
+            Ok(())
+        } else {
+            Err(Error::ExternalWalletMissing)
+        }
+    }
+
+    pub fn external_send_to_self(&mut self, amount: Amount) -> Result<(), Error> {
+        let address = self.get_new_address()?;
+        self.external_send_to_address(SendToAddress { amount, address })
+    }
+
+    /// Send all the funds of the external wallet back to rpcwallet 'regtest'
+    pub fn external_sweep(&mut self) -> Result<(), Error> {
+        if self.external_client.is_none() {
+            return Err(Error::ExternalWalletMissing);
+        }
+        let address = self.get_new_address()?;
+        if let Some(client) = self.external_client.as_ref() {
+            client
+                .call::<serde_json::Value>("sendall", &[serde_json::json!([address.to_string()])])
+                .map_err(Error::from)?;
+            Ok(())
+        } else {
+            Err(Error::ExternalWalletMissing)
+        }
+    }
+
+    pub fn maybe_send_back(&mut self) -> Result<(), Error> {
+        let send = if let Some(params) = self.send_back.as_mut() {
+            let left = params
+                .blocks_left
+                .unwrap_or(params.blocks)
+                .saturating_sub(1);
+            if left == 0 {
+                params.blocks_left = Some(params.blocks);
+                Some((params.sweep, params.amount_min, params.amount_max))
+            } else {
+                params.blocks_left = Some(left);
+                None
+            }
+        } else {
+            None
+        };
+
+        match send {
+            Some((true, _, _)) => self.external_sweep(),
+            Some((false, min, max)) => {
+                let amount = traffic::amount(&mut self.rng, min, max);
+                self.external_send_to_self(amount)
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub fn decode_psbt(&self, psbt: &str) -> Result<String, Error> {
+        if let Some(client) = self.client.as_ref() {
+            let decoded: DecodedPsbt = client
+                .call("decodepsbt", &[psbt.into()])
+                .map_err(Error::from)?;
+            let analyzed: AnalyzedPsbt = client
+                .call("analyzepsbt", &[psbt.into()])
+                .map_err(Error::from)?;
+            Ok(psbt::summary(&decoded, &analyzed))
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    pub fn sign_psbt(&self, psbt: &str) -> Result<(String, bool), Error> {
+        if let Some(client) = self.wallet_client.as_ref() {
+            let result = client
+                .wallet_process_psbt(psbt, Some(true), None, None)
+                .map_err(Error::from)?;
+            Ok((result.psbt, result.complete))
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    pub fn combine_psbt(&self, psbts: &[String]) -> Result<String, Error> {
+        if let Some(client) = self.client.as_ref() {
+            client.combine_psbt(psbts).map_err(Error::from)
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// Finalize `psbt`, if `broadcast` is set the extracted transaction is
+    /// broadcasted and its txid is returned, else the finalized PSBT is returned.
+    pub fn finalize_psbt(&self, psbt: &str, broadcast: bool) -> Result<String, Error> {
+        if let Some(client) = self.client.as_ref() {
+            let result = client
+                .finalize_psbt(psbt, Some(broadcast))
+                .map_err(Error::from)?;
+            if !result.complete {
+                return Err(Error::PsbtNotComplete);
+            }
+            match (broadcast, result.hex, result.psbt) {
+                (true, Some(hex), _) => Ok(client
+                    .send_raw_transaction(&hex)
+                    .map_err(Error::from)?
+                    .to_string()),
+                (false, _, Some(psbt)) => Ok(psbt),
+                _ => Err(Error::PsbtNotComplete),
+            }
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    pub fn decode_raw_tx(hex: &str, network: Network) -> Result<String, Error> {
+        let tx = rawtx::decode(hex).map_err(Error::DecodeTx)?;
+        Ok(rawtx::summary(&tx, network))
+    }
+
+    /// Run `testmempoolaccept` against `hex`, returns the txid and vsize on success.
+    pub fn test_raw_tx(&self, hex: &str) -> Result<String, Error> {
+        if let Some(client) = self.client.as_ref() {
+            let result = client
+                .test_mempool_accept(&[hex])
+                .map_err(Error::from)?
+                .into_iter()
+                .next()
+                .ok_or(Error::TxRejected(
+                    "empty testmempoolaccept result".to_string(),
+                ))?;
+            if result.allowed {
+                Ok(format!(
+                    "Transaction {} accepted (vsize: {}, fee: {})",
+                    result.txid,
+                    result.vsize.unwrap_or_default(),
+                    result
+                        .fees
+                        .map(|f| f.base.to_string())
+                        .unwrap_or_else(|| "?".to_string())
+                ))
+            } else {
+                Err(Error::TxRejected(
+                    result
+                        .reject_reason
+                        .unwrap_or_else(|| "unknown reason".to_string()),
+                ))
+            }
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// List the mempool transactions, parents first.
+    pub fn list_mempool(&self) -> Result<Vec<MempoolTx>, Error> {
+        Self::mempool(self.client.as_ref().ok_or(Error::NotConnected)?)
+    }
+
+    fn mempool(client: &Client) -> Result<Vec<MempoolTx>, Error> {
+        let mut mempool = client
+            .get_raw_mempool_verbose()
+            .map_err(Error::from)?
+            .into_iter()
+            .map(|(txid, entry)| MempoolTx {
+                txid,
+                vsize: entry.vsize,
+                fee: entry.fees.base,
+                ancestors: entry.ancestor_count,
+                time: entry.time,
+                depends: entry.depends,
+            })
+            .collect::<Vec<_>>();
+        mempool.sort_by_key(|tx| (tx.ancestors, tx.time, tx.txid));
+        Ok(mempool)
+    }
+
+    /// Mine a block containing exactly the transactions of `params` w/ `generateblock`.
+    pub fn build_block(&mut self, params: &BuildBlock) -> Result<BlockHash, Error> {
+        // generateblock does not sign signet blocks
+        if self.network != Network::Regtest {
+            return Err(Error::MiningUnavailable(self.network));
+        }
+        let (client, wallet_client) = match (&self.client, &self.wallet_client) {
+            (Some(client), Some(wallet_client)) => (client, wallet_client),
+            _ => return Err(Error::NotConnected),
+        };
+        let address = Self::payout_address(
+            &mut self.rng,
+            &self.secp,
+            wallet_client,
+            &params.payout,
+            0,
+            self.network,
+            &self.address_mix,
+        )?;
+        let transactions = params
+            .txids
+            .iter()
+            .map(|txid| txid.to_string())
+            .chain(params.raw_txs.iter().map(|hex| hex.trim().to_string()))
+            .collect::<Vec<_>>();
+        let result = client
+            .call::<serde_json::Value>(
+                "generateblock",
+                &[address.to_string().into(), serde_json::json!(transactions)],
+            )
+            .map_err(Error::from)?;
+        result["hash"]
+            .as_str()
+            .and_then(|hash| BlockHash::from_str(hash).ok())
+            .ok_or_else(|| {
+                Error::InvalidParameter(format!("unexpected generateblock result {}", result))
+            })
+    }
+
+    /// Mine a block w/ `generateblock` w/o the mempool transactions excluded by `rules`
+    /// (and their descendants), returns the count of excluded transactions.
+    pub fn mine_censored(
+        client: &Client,
+        address: &Address,
+        rules: &[CensorRule],
+    ) -> Result<usize, Error> {
+        let need_scripts = rules
+            .iter()
+            .any(|rule| matches!(rule.criteria, CensorCriteria::Descriptor { .. }));
+        let mut mempool = Vec::new();
+        for tx in Self::mempool(client)? {
+            let scripts = if need_scripts {
+                client
+                    .get_raw_transaction(&tx.txid, None)
+                    .map_err(Error::from)?
+                    .output
+                    .into_iter()
+                    .map(|output| output.script_pubkey)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            mempool.push((tx, scripts));
+        }
+        let (included, censored) = miner::censored_block(&mempool, rules);
+        let included = included
+            .iter()
+            .map(|txid| txid.to_string())
+            .collect::<Vec<_>>();
+        client
+            .call::<serde_json::Value>(
+                "generateblock",
+                &[address.to_string().into(), serde_json::json!(included)],
+            )
+            .map_err(Error::from)?;
+        Ok(censored)
+    }
+
+    fn update_mempool(&self) {
+        match self.list_mempool() {
+            Ok(mempool) => self.send_to_gui(BitcoinMessage::Mempool(mempool)),
+            Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                e.report("Fail to list mempool"),
+            )),
+        }
+    }
+
+    /// Broadcast `hex`, if `mine` is set a block containing (only) this transaction and
+    /// its unconfirmed ancestors is mined on top of it.
+    pub fn broadcast_raw_tx(&mut self, hex: &str, mine: bool) -> Result<String, Error> {
+        if let Some(client) = self.client.as_ref() {
+            let backend = if mine { Some(self.backend()?) } else { None };
+            let txid = client.send_raw_transaction(hex).map_err(Error::from)?;
+            let Some(backend) = backend else {
+                return Ok(txid.to_string());
+            };
+            let address = Self::get_random_address(
+                &mut self.rng,
+                &self.secp,
+                self.network,
+                &self.address_mix,
+            );
+            match backend {
+                MiningBackend::GenerateToAddress => {
+                    // generateblock rejects a child w/o its parents
+                    let ancestors = client
+                        .call::<Vec<Txid>>("getmempoolancestors", &[txid.to_string().into()])
+                        .map_err(Error::from)?;
+                    // the mempool is sorted parents first
+                    let mut transactions = Self::mempool(client)?
+                        .into_iter()
+                        .filter(|tx| ancestors.contains(&tx.txid))
+                        .map(|tx| tx.txid.to_string())
+                        .collect::<Vec<_>>();
+                    transactions.push(txid.to_string());
+                    client
+                        .call::<serde_json::Value>(
+                            "generateblock",
+                            &[address.to_string().into(), serde_json::json!(transactions)],
+                        )
+                        .map_err(Error::from)?;
+                }
+                // the template is built from the mempool, so it contains the transaction
+                backend => backend.mine(client, &self.secp, &address)?,
+            }
+            Ok(txid.to_string())
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    fn handle_psbt(&self, result: Result<String, Error>) {
+        match result {
+            Ok(psbt) => {
+                self.send_to_gui(BitcoinMessage::PsbtUpdated(psbt));
+                self.send_to_gui(BitcoinMessage::PsbtResponse(true));
+            }
+            Err(e) => {
+                self.send_to_gui(BitcoinMessage::SendMessage(e.report("PSBT error")));
+                self.send_to_gui(BitcoinMessage::PsbtResponse(false));
+            }
+        }
+    }
+
+    pub fn set_seed(&mut self, seed: Option<JobSeed>) {
+        let JobSeed { seed, job } = seed.unwrap_or_else(|| JobSeed {
+            seed: rand::thread_rng().gen(),
+            job: 0,
+        });
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.seed_jobs = job;
+        self.auto_block_runs = 0;
+        self.send_to_gui(BitcoinMessage::SendMessage(format!(
+            "Random seed: {}",
+            self.seed
+        )));
+        self.send_to_gui(BitcoinMessage::Seed(self.seed));
+    }
+
+    /// Returns the backend able to mine blocks on the node network: `generatetoaddress`
+    /// on regtest, signed blocks on signet if the challenge key is set.
+    pub fn backend(&self) -> Result<MiningBackend, Error> {
+        match (self.network, self.signet_key) {
+            (Network::Regtest, _) => Ok(MiningBackend::GenerateToAddress),
+            (Network::Signet, Some(key)) => Ok(MiningBackend::Signet(key)),
+            (network, _) => Err(Error::MiningUnavailable(network)),
+        }
+    }
+
+    /// Parse the WIF `key` and check it can sign blocks of the node signet.
+    pub fn set_signet_key(&mut self, key: &str) -> Result<(), Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        let key = PrivateKey::from_wif(key.trim()).map_err(|e| Error::Signet(e.to_string()))?;
+        signet::check_key(client, &self.secp, &key)?;
+        self.signet_key = Some(key);
+        Ok(())
+    }
+
+    /// Detect the network of the node.
+    pub fn detect_network(&mut self) -> Result<Network, Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        self.network = client.get_blockchain_info().map_err(Error::from)?.chain;
+        Ok(self.network)
+    }
+
+    /// Spawn `kind` as a job on a blocking thread.
+    pub fn spawn_job(&mut self, kind: JobKind) -> Result<JobId, Error> {
+        if !self.is_connected() {
+            return Err(Error::NotConnected);
+        }
+        let backend = if kind.mines() {
+            Some(self.backend()?)
+        } else {
+            None
+        };
+        let id = self.next_job_id;
+        let seed = JobSeed {
+            seed: self.seed,
+            job: self.seed_jobs,
+        };
+        let cancel = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            info: JobInfo {
+                id,
+                label: kind.to_string(),
+                kind: kind.clone(),
+                done: 0,
+                total: 0,
+                index: None,
+                last_txid: None,
+                eta: None,
+                status: JobStatus::Running,
+                seed,
+                address_mix: self.address_mix.clone(),
+            },
+            client: self.rpc_client(None)?,
+            wallet_client: self.rpc_client(Some(&self.wallet_name))?,
+            secp: self.secp.clone(),
+            network: self.network,
+            backend,
+            rng: StdRng::seed_from_u64(seed.derive()),
+            address_mix: self.address_mix.clone(),
+            sender: self.sender.clone(),
+            loopback: self.loopback.clone(),
+            cancel: cancel.clone(),
+            started: Instant::now(),
+        };
+        self.next_job_id += 1;
+        self.seed_jobs += 1;
+        self.jobs.insert(id, cancel);
+        self.send_to_gui(BitcoinMessage::SendMessage(format!(
+            "Job #{} ({}) started, seed {}, address types {}",
+            id, job.info.label, seed, self.address_mix
+        )));
+        job.send_to_gui(BitcoinMessage::JobUpdate(job.info.clone()));
+        tokio::task::spawn_blocking(move || job.run(kind));
+        Ok(id)
+    }
+
+    fn handle_spawn_job(&mut self, kind: JobKind) {
+        if let Err(e) = self.spawn_job(kind) {
+            self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to start job")));
+        }
+    }
+
+    pub fn cancel_job(&self, id: JobId) {
+        if let Some(cancel) = self.jobs.get(&id) {
+            cancel.store(true, Ordering::Relaxed);
         }
     }
 
@@ -225,8 +1793,30 @@ impl BitcoinD {
     }
 
     pub fn disconnect(&mut self) {
+        // the node keeps its mocktime, reset it so the next session does not start w/ a
+        // mocktime it does not know of
+        if self.mock_time.is_some() {
+            if let Err(e) = self.set_mock_time(0) {
+                log::warn!("Fail to reset the node mocktime: {}", e);
+            }
+        }
+        self.health_sender = None;
+        self.node_healthy = true;
+        self.network = Network::Regtest;
+        self.signet_key = None;
         self.client = None;
         self.wallet_client = None;
+        self.external_client = None;
+        self.send_back = None;
+        for cancel in self.jobs.values() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.schedules.clear();
+        self.scheduler_sender = None;
+        self.actors.clear();
+        self.actor_clients.clear();
+        self.mock_time = None;
+        self.mock_time_step = None;
         self.auth = None;
         self.send_to_gui(BitcoinMessage::Connected(false));
     }
@@ -235,50 +1825,62 @@ impl BitcoinD {
         if let Some(client) = self.client.as_ref() {
             match client.get_blockchain_info() {
                 Ok(info) => Ok(info.blocks),
-                Err(e) => Err(Error::Rpc(e)),
+                Err(e) => Err(Error::from(e)),
             }
         } else {
             Err(Error::NotConnected)
         }
     }
 
-    pub fn get_balance(&self) -> Result<Amount, Error> {
-        if let Some(client) = self.wallet_client.as_ref() {
-            client.get_balance(None, None).map_err(Error::Rpc)
+    pub fn get_median_time(&self) -> Result<u64, Error> {
+        if let Some(client) = self.client.as_ref() {
+            match client.get_blockchain_info() {
+                Ok(info) => Ok(info.median_time),
+                Err(e) => Err(Error::from(e)),
+            }
         } else {
             Err(Error::NotConnected)
         }
     }
 
-    pub fn get_random_address(secp: &miniscript::bitcoin::secp256k1::Secp256k1<All>) -> Address {
-        let prv = PrivateKey::generate(Network::Regtest);
-        let pb = prv.public_key(secp);
-        Address::p2pkh(pb, Network::Regtest)
+    pub fn get_balances(&self) -> Result<Balances, Error> {
+        Self::balances(self.wallet_client.as_ref().ok_or(Error::NotConnected)?)
     }
 
-    pub fn get_random_tx_count(send: u32, block: u32) -> u32 {
-        const MULTIPLIER: i32 = 10_000;
-        const MAX_TX_PER_BLOCK: i32 = 10_000;
-        let send_per_block =
-            ((send as f64 / block as f64).min(MAX_TX_PER_BLOCK as f64) * MULTIPLIER as f64) as i32;
-
-        let mut rng = rand::thread_rng();
+    pub fn balances(wallet_client: &Client) -> Result<Balances, Error> {
+        let balances = wallet_client.get_balances().map_err(Error::from)?.mine;
+        Ok(Balances {
+            trusted: balances.trusted,
+            untrusted_pending: balances.untrusted_pending,
+            immature: balances.immature,
+        })
+    }
 
-        if send_per_block <= MULTIPLIER {
-            // less than one tx/block
-            let random = rng.gen_range(0..MULTIPLIER);
-            if random > send_per_block {
-                1
-            } else {
-                0
+    /// New address of a random key (coins sent to it are burned), of a type drawn
+    /// from `mix`.
+    pub fn get_random_address<R: Rng>(
+        rng: &mut R,
+        secp: &miniscript::bitcoin::secp256k1::Secp256k1<All>,
+        network: Network,
+        mix: &AddressMix,
+    ) -> Address {
+        let address_type = mix.random_type(rng);
+        // drawn from `rng` (not the OS) so a seed reproduces the addresses
+        let secret = loop {
+            if let Ok(secret) = SecretKey::from_slice(&rng.gen::<[u8; 32]>()) {
+                break secret;
             }
-        } else {
-            // more than one tx per block
-            let random = rng.gen_range(0..send_per_block);
-            if random > MULTIPLIER {
-                (random / MULTIPLIER) as u32
-            } else {
-                0
+        };
+        let prv = PrivateKey::new(secret, network);
+        let pb = prv.public_key(secp);
+        let compressed = CompressedPublicKey(pb.inner);
+        match address_type {
+            AddressType::P2pkh => Address::p2pkh(pb, network),
+            AddressType::P2shP2wpkh => Address::p2shwpkh(&compressed, network),
+            AddressType::P2wpkh => Address::p2wpkh(&compressed, network),
+            AddressType::P2wsh => Address::p2wsh(&ScriptBuf::new_p2pk(&pb), network),
+            AddressType::P2tr => {
+                Address::p2tr(secp, secret.x_only_public_key(secp).0, None, network)
             }
         }
     }
@@ -292,123 +1894,211 @@ impl BitcoinD {
         Ok(())
     }
 
-    pub fn invalidate_at_block_height(&self, block_height: u64) -> Result<(), Error> {
+    pub fn invalidate_at_block_height(&self, block_height: u64) -> Result<(), Error> {
+        if let Some(client) = self.client.as_ref() {
+            let block_hash = client.get_block_hash(block_height).map_err(Error::from)?;
+            client.invalidate_block(&block_hash).map_err(Error::from)
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// Set the node mocktime to `timestamp` then mine blocks (stamped at
+    /// `timestamp`) until the median-time-past reach it.
+    pub fn advance_mtp(&mut self, timestamp: u64) -> Result<(), Error> {
+        self.set_mock_time(timestamp)?;
+        self.spawn_job(JobKind::MineUntilMtp(timestamp))?;
+        Ok(())
+    }
+
+    /// Set the node mocktime, 0 disable mocktime.
+    pub fn set_mock_time(&mut self, timestamp: u64) -> Result<(), Error> {
+        if let Some(client) = self.client.as_ref() {
+            client
+                .call::<()>("setmocktime", &[timestamp.into()])
+                .map_err(Error::from)?;
+            self.mock_time = if timestamp == 0 {
+                None
+            } else {
+                Some(timestamp)
+            };
+            self.send_to_gui(BitcoinMessage::UpdateMockTime(self.mock_time));
+            Ok(())
+        } else {
+            Err(Error::NotConnected)
+        }
+    }
+
+    /// Move the mocktime `seconds` forward, starting from the actual mocktime
+    /// if any, else from the latest of the wall clock and the tip timestamp.
+    pub fn advance_mock_time(&mut self, seconds: u64) -> Result<(), Error> {
+        let start = match self.mock_time {
+            Some(time) => time,
+            None => {
+                let now = time::SystemTime::now()
+                    .duration_since(time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default();
+                now.max(self.get_tip_time()?)
+            }
+        };
+        self.set_mock_time(start + seconds)
+    }
+
+    pub fn get_tip_time(&self) -> Result<u64, Error> {
         if let Some(client) = self.client.as_ref() {
-            let block_hash = client.get_block_hash(block_height).map_err(Error::Rpc)?;
-            client.invalidate_block(&block_hash).map_err(Error::Rpc)
+            let hash = client.get_best_block_hash().map_err(Error::from)?;
+            let header = client.get_block_header(&hash).map_err(Error::from)?;
+            Ok(header.time as u64)
         } else {
             Err(Error::NotConnected)
         }
     }
 
-    pub fn generate(&self, blocks: u32) -> Result<(), Error> {
-        let address = Self::get_random_address(&self.secp);
-        self.generate_to_address(GenerateToAddress { blocks, address })?;
+    /// If a mocktime step is set, move the mocktime forward after a block
+    /// have been mined by the autoblock miner.
+    pub fn maybe_advance_mock_time(&mut self) -> Result<(), Error> {
+        if let Some(step) = self.mock_time_step {
+            self.advance_mock_time(step)?;
+        }
         Ok(())
     }
 
-    pub fn generate_to_address(&self, params: GenerateToAddress) -> Result<(), Error> {
-        if let Some(client) = self.client.as_ref() {
-            client
-                .generate_to_address(params.blocks as u64, &params.address)
-                .map_err(Error::Rpc)?;
-            Ok(())
-        } else {
-            Err(Error::NotConnected)
-        }
+    /// New address of the controlled wallet, of a type drawn from the address mix.
+    pub fn get_new_address(&mut self) -> Result<Address, Error> {
+        let client = self.wallet_client.as_ref().ok_or(Error::NotConnected)?;
+        Self::new_wallet_address(&mut self.rng, client, &self.address_mix)
     }
 
-    pub fn get_new_address(&self) -> Result<String, Error> {
-        if let Some(client) = self.wallet_client.as_ref() {
-            Ok(client
-                .get_new_address(None, None)
-                .map_err(Error::Rpc)?
-                .assume_checked()
-                .to_string())
-        } else {
-            Err(Error::NotConnected)
+    pub fn new_wallet_address<R: Rng>(
+        rng: &mut R,
+        wallet_client: &Client,
+        mix: &AddressMix,
+    ) -> Result<Address, Error> {
+        Ok(wallet_client
+            .get_new_address(None, mix.wallet_type(rng))
+            .map_err(Error::from)?
+            .assume_checked())
+    }
+
+    /// Send from `wallet_client`, the change address type is drawn from `mix` (`send` RPC),
+    /// or the node default if the mix has no wallet type (`sendtoaddress`).
+    pub fn wallet_send<R: Rng>(
+        rng: &mut R,
+        wallet_client: &Client,
+        params: &SendToAddress,
+        mix: &AddressMix,
+    ) -> Result<Txid, Error> {
+        match mix.wallet_type(rng) {
+            None => wallet_client
+                .send_to_address(
+                    &params.address,
+                    params.amount,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .map_err(Error::from),
+            Some(change_type) => Self::send_w_change_type(
+                wallet_client,
+                Self::amounts(&[(params.address.clone(), params.amount)]),
+                change_type,
+            ),
         }
     }
 
-    pub fn generate_to_self(&self, blocks: u32) -> Result<(), Error> {
-        if let Some(client) = self.wallet_client.as_ref() {
-            let address = client
-                .get_new_address(None, None)
-                .map_err(Error::Rpc)?
-                .assume_checked();
-            self.generate_to_address(GenerateToAddress { blocks, address })
-        } else {
-            Err(Error::NotConnected)
+    /// Send `outputs` in a single transaction from `wallet_client`, the change address type
+    /// is drawn from `mix` (`send` RPC), or the node default (`sendmany`).
+    pub fn wallet_send_many<R: Rng>(
+        rng: &mut R,
+        wallet_client: &Client,
+        outputs: &[(Address, Amount)],
+        mix: &AddressMix,
+    ) -> Result<Txid, Error> {
+        match mix.wallet_type(rng) {
+            None => {
+                let txid = wallet_client
+                    .call::<String>("sendmany", &["".into(), Self::amounts(outputs).into()])
+                    .map_err(Error::from)?;
+                Txid::from_str(&txid).map_err(|e| Error::TxRejected(e.to_string()))
+            }
+            Some(change_type) => {
+                Self::send_w_change_type(wallet_client, Self::amounts(outputs), change_type)
+            }
         }
     }
 
-    pub fn generate_to_descriptor(&self, params: GenerateToDescriptor) -> Result<(), Error> {
-        let (start, end) = (params.start_index, params.start_index + params.blocks);
-        let descriptor =
-            Descriptor::from_str(&params.descriptor).map_err(|_| Error::ParseDescriptor)?;
+    /// `{address: amount}` outputs of the send RPCs.
+    fn amounts(outputs: &[(Address, Amount)]) -> serde_json::Map<String, serde_json::Value> {
+        outputs
+            .iter()
+            .map(|(address, amount)| {
+                (
+                    address.to_string(),
+                    serde_json::Value::from(amount.to_btc()),
+                )
+            })
+            .collect()
+    }
 
-        for index in start..end {
-            let address = Self::address_from_descriptor(&self.secp, descriptor.clone(), index)?;
-            self.generate_to_address(GenerateToAddress { blocks: 1, address })?;
-            self.send_to_gui(BitcoinMessage::IncrementGenerateDescriptorIndex);
-        }
-        Ok(())
+    fn send_w_change_type(
+        wallet_client: &Client,
+        outputs: serde_json::Map<String, serde_json::Value>,
+        change_type: json::AddressType,
+    ) -> Result<Txid, Error> {
+        let result = wallet_client
+            .call::<serde_json::Value>(
+                "send",
+                &[
+                    outputs.into(),
+                    serde_json::Value::Null,
+                    serde_json::Value::Null,
+                    serde_json::Value::Null,
+                    serde_json::json!({ "change_type": change_type }),
+                ],
+            )
+            .map_err(Error::from)?;
+        result
+            .get("txid")
+            .and_then(|txid| txid.as_str())
+            .and_then(|txid| Txid::from_str(txid).ok())
+            .ok_or_else(|| Error::TxRejected(format!("send returned no txid: {}", result)))
     }
 
     pub fn address_from_descriptor(
         secp: &miniscript::bitcoin::secp256k1::Secp256k1<All>,
         descriptor: Descriptor<DescriptorPublicKey>,
         index: u32,
+        network: Network,
     ) -> Result<Address, Error> {
         descriptor
             .into_single_descriptors()
-            .map_err(|_| Error::ParseDescriptor)?
+            .map_err(|e| Error::ParseDescriptor {
+                message: e.to_string(),
+                position: None,
+            })?
             .into_iter()
             // we take the first multipath as receive path
             .next()
-            .ok_or(Error::ParseDescriptor)?
+            .ok_or(Error::DeriveDescriptor)?
             .derived_descriptor(secp, index)
             .map_err(|_| Error::DeriveDescriptor)?
-            .address(Network::Regtest)
+            .address(network)
             .map_err(|_| Error::DeriveDescriptor)
     }
 
-    pub fn send_to_address(&self, params: SendToAddress) -> Result<(), Error> {
-        if let Some(client) = self.wallet_client.as_ref() {
-            client
-                .send_to_address(
-                    &params.address,
-                    params.amount,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                )
-                .map_err(Error::Rpc)?;
-            Ok(())
-        } else {
-            Err(Error::NotConnected)
-        }
-    }
-
-    pub fn send_to_descriptor(&self, params: SendToDescriptor) -> Result<(), Error> {
-        let (start, end) = (params.start_index, params.start_index + params.count);
-        let descriptor = Descriptor::<DescriptorPublicKey>::from_str(&params.descriptor)
-            .map_err(|_| Error::DeriveDescriptor)?;
-        for index in start..end {
-            let amount = Self::random_amount(params.amount_min, params.amount_max);
-            let address = Self::address_from_descriptor(&self.secp, descriptor.clone(), index)?;
-            self.send_to_address(SendToAddress { amount, address })?;
-            self.send_to_gui(BitcoinMessage::IncrementSendDescriptorIndex);
-        }
+    pub fn send_to_address(&mut self, params: SendToAddress) -> Result<(), Error> {
+        let client = self.wallet_client.as_ref().ok_or(Error::NotConnected)?;
+        Self::wallet_send(&mut self.rng, client, &params, &self.address_mix)?;
         Ok(())
     }
 
     pub fn maybe_send_every_block(&mut self) -> Result<(), Error> {
         if let Some(params) = self.send_every_block.as_mut() {
-            let tx_count = Self::get_random_tx_count(params.count, params.blocks);
+            let tx_count = traffic::tx_count(&mut self.rng, params.count, params.blocks);
 
             let start = if let Some(index) = params.actual_index {
                 index
@@ -420,10 +2110,10 @@ impl BitcoinD {
             let (min, max) = (params.amount_min, params.amount_max);
             let descriptor = params.descriptor.clone();
             for index in start..end {
-                let amount = Self::random_amount(min, max);
-                let descriptor = Descriptor::<DescriptorPublicKey>::from_str(&descriptor)
-                    .map_err(|_| Error::ParseDescriptor)?;
-                let address = Self::address_from_descriptor(&self.secp, descriptor, index)?;
+                let amount = traffic::amount(&mut self.rng, min, max);
+                let descriptor = parse_descriptor(&descriptor)?;
+                let address =
+                    Self::address_from_descriptor(&self.secp, descriptor, index, self.network)?;
                 self.send_to_address(SendToAddress { amount, address })?;
             }
         }
@@ -431,11 +2121,198 @@ impl BitcoinD {
         Ok(())
     }
 
-    pub fn random_amount(min: Amount, max: Amount) -> Amount {
-        let mut rng = rand::thread_rng();
-        let (min, max) = (min.to_sat(), max.to_sat());
-        let random = rng.gen_range(min..max);
-        Amount::from_sat(random)
+    pub fn add_schedule(&mut self, schedule: Schedule) -> Result<ScheduleId, Error> {
+        if let ScheduleTarget::Addresses(addresses) = &schedule.target {
+            if addresses.is_empty() {
+                return Err(Error::EmptyAddressList);
+            }
+        }
+        let height = self.get_block_height()?;
+        let id = self.next_schedule_id;
+        self.next_schedule_id += 1;
+        self.schedules
+            .insert(id, ScheduledSend::new(id, schedule, height));
+        if self.scheduler_sender.is_none() {
+            self.start_scheduler();
+        }
+        Ok(id)
+    }
+
+    pub fn remove_schedule(&mut self, id: ScheduleId) {
+        self.schedules.remove(&id);
+        if self.schedules.is_empty() {
+            // dropping the sender stops the ticker
+            self.scheduler_sender = None;
+        }
+    }
+
+    /// Spawn a ticker driving the scheduled sends, the block based ones are checked on
+    /// every tick too so blocks not mined by Minta are taken into account.
+    fn start_scheduler(&mut self) {
+        let (sender, receiver) = async_channel::bounded::<()>(1);
+        self.scheduler_sender = Some(sender);
+        let loopback = self.loopback.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = receiver.recv() => break,
+                    _ = tokio::time::sleep(SCHEDULER_TICK) => {}
+                }
+                if loopback.try_send(BitcoinMessage::ScheduleTick).is_err() {
+                    break;
+                }
+            }
+            log::info!("Scheduler stopped");
+        });
+    }
+
+    /// Send the due scheduled payments on a round thread, the tick is skipped if the
+    /// previous round is still sending.
+    pub fn run_schedules(&mut self) -> Result<(), Error> {
+        if self.schedules.is_empty() || self.sending_schedules {
+            return Ok(());
+        }
+        let round = self.round()?;
+        let schedules = self.schedules.values().cloned().collect();
+        self.sending_schedules = true;
+        tokio::task::spawn_blocking(move || round.send_schedules(schedules));
+        Ok(())
+    }
+
+    fn round(&mut self) -> Result<Round, Error> {
+        Ok(Round {
+            client: self.rpc_client(None)?,
+            wallet_client: self.rpc_client(Some(&self.wallet_name))?,
+            secp: self.secp.clone(),
+            network: self.network,
+            rng: StdRng::seed_from_u64(self.rng.gen()),
+            address_mix: self.address_mix.clone(),
+            sender: self.sender.clone(),
+            loopback: self.loopback.clone(),
+        })
+    }
+
+    fn update_schedules(&self) {
+        self.send_to_gui(BitcoinMessage::Schedules(
+            self.schedules.values().cloned().collect(),
+        ));
+    }
+
+    /// Create (or load) the actor wallet, fund it from the controlled wallet and
+    /// register the actor.
+    pub fn add_actor(&mut self, params: NewActor) -> Result<ActorId, Error> {
+        let client = self.client.as_ref().ok_or(Error::NotConnected)?;
+        if params.name.is_empty()
+            || !params
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(Error::InvalidParameter(format!(
+                "invalid actor name '{}' (letters, digits, '-' and '_' only)",
+                params.name
+            )));
+        }
+        if self.actors.values().any(|actor| actor.name == params.name) {
+            return Err(Error::InvalidParameter(format!(
+                "actor '{}' already exists",
+                params.name
+            )));
+        }
+        let id = self.next_actor_id;
+        let actor = Actor::new(
+            id,
+            params.clone(),
+            self.get_block_height()?,
+            self.get_tip_time()?,
+        );
+        let wallet = actor.wallet();
+        match Self::reload_wallet(client, &wallet) {
+            Err(Error::WalletNotFound(_)) => {
+                log::info!("Actor wallet {} does not exists, creating it...", wallet);
+                client
+                    .create_wallet(&wallet, None, None, None, None)
+                    .map_err(Error::from)?;
+            }
+            result => result?,
+        }
+        let actor_client = self.rpc_client(Some(&wallet))?;
+        if params.funding > Amount::ZERO {
+            let address =
+                Self::new_wallet_address(&mut self.rng, &actor_client, &self.address_mix)?;
+            self.send_to_address(SendToAddress {
+                amount: params.funding,
+                address,
+            })?;
+        }
+        self.next_actor_id += 1;
+        self.actors.insert(id, actor);
+        self.actor_clients.insert(id, actor_client);
+        Ok(id)
+    }
+
+    /// Sweep the funds of the actor back to the controlled wallet, unload its wallet and
+    /// unregister it, the actor is kept if any step fails.
+    pub fn remove_actor(&mut self, id: ActorId) -> Result<(), Error> {
+        let Some(wallet) = self.actors.get(&id).map(|actor| actor.wallet()) else {
+            return Ok(());
+        };
+        let balance = match self.actor_clients.get(&id) {
+            Some(client) => client.get_balance(None, None).map_err(Error::from)?,
+            None => Amount::ZERO,
+        };
+        if balance > Amount::ZERO {
+            let address = self.get_new_address()?;
+            if let Some(client) = self.actor_clients.get(&id) {
+                client
+                    .call::<serde_json::Value>(
+                        "sendall",
+                        &[serde_json::json!([address.to_string()])],
+                    )
+                    .map_err(Error::from)?;
+            }
+        }
+        // not `unload_wallet()`, that refuses to unload the wallet of a registered actor
+        self.client
+            .as_ref()
+            .ok_or(Error::NotConnected)?
+            .call::<serde_json::Value>("unloadwallet", &[wallet.into()])
+            .map_err(Error::from)?;
+        self.actors.remove(&id);
+        self.actor_clients.remove(&id);
+        Ok(())
+    }
+
+    /// Let every actor play its profile on a round thread, called after each autoblock
+    /// block. Skipped if the previous round is still playing, the profiles catch up on the
+    /// next one.
+    pub fn run_actors(&mut self) -> Result<(), Error> {
+        if self.actors.is_empty() || self.playing_actors {
+            return Ok(());
+        }
+        let round = self.round()?;
+        let actors = self
+            .actors
+            .values()
+            .map(|actor| Ok((actor.clone(), self.rpc_client(Some(&actor.wallet()))?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.playing_actors = true;
+        tokio::task::spawn_blocking(move || round.play_actors(actors));
+        Ok(())
+    }
+
+    /// Refresh the actor balances and send the actors to the GUI.
+    fn update_actors(&mut self) {
+        for (id, client) in &self.actor_clients {
+            if let (Some(actor), Ok(balance)) =
+                (self.actors.get_mut(id), client.get_balance(None, None))
+            {
+                actor.balance = balance;
+            }
+        }
+        self.send_to_gui(BitcoinMessage::Actors(
+            self.actors.values().cloned().collect(),
+        ));
     }
 
     pub fn handle_connect(&mut self) {
@@ -445,14 +2322,26 @@ impl BitcoinD {
                     self.client = Some(client.0);
                     self.wallet_client = Some(client.1);
                     self.send_to_gui(BitcoinMessage::Connected(true));
+                    match self.detect_network() {
+                        Ok(network) => {
+                            log::info!("Network: {}", network);
+                            self.send_to_gui(BitcoinMessage::UpdateNetwork(network));
+                        }
+                        Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                            e.report("Fail to detect network"),
+                        )),
+                    }
+                    self.update_wallets();
                     log::info!("Connected!");
+                    if let Err(e) = self.start_health_monitor() {
+                        self.send_to_gui(BitcoinMessage::SendMessage(
+                            e.report("Fail to start health monitor"),
+                        ));
+                    }
                 }
                 Err(e) => {
                     log::error!("Fail to connect: {:?}", e);
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
-                        "Fail to connect: {:?}",
-                        e
-                    )));
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to connect")));
                     self.send_to_gui(BitcoinMessage::Connected(false));
                 }
             }
@@ -467,128 +2356,453 @@ impl BitcoinD {
     }
 
     pub fn handle_message(&mut self, msg: BitcoinMessage) {
-        match (msg, &self.mining_busy) {
-            (BitcoinMessage::SetCredentials { address, auth }, _) => {
+        match msg {
+            BitcoinMessage::SetCredentials {
+                address,
+                auth,
+                wallet,
+            } => {
                 self.address = Some(address);
                 self.auth = Some(auth);
+                self.wallet_name = wallet;
+            }
+            BitcoinMessage::ListWallets => self.update_wallets(),
+            BitcoinMessage::SetSeed(seed) => self.set_seed(seed),
+            BitcoinMessage::SetAddressMix(mix) => self.address_mix = mix,
+            BitcoinMessage::SetSignetKey(key) => match self.set_signet_key(&key) {
+                Ok(()) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        "Signet challenge key set".to_string(),
+                    ));
+                    self.send_to_gui(BitcoinMessage::SignetKeySet(true));
+                }
+                Err(e) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to set signet key"),
+                    ));
+                    self.send_to_gui(BitcoinMessage::SignetKeySet(false));
+                }
+            },
+            BitcoinMessage::CreateWallet(params) => {
+                if let Err(e) = self.create_wallet(&params) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to create wallet"),
+                    ));
+                } else {
+                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                        "Wallet {} created",
+                        params.name
+                    )));
+                }
+                self.update_wallets();
+            }
+            BitcoinMessage::LoadWallet(name) => {
+                if let Err(e) = self.load_wallet(&name) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to load wallet")));
+                }
+                self.update_wallets();
+            }
+            BitcoinMessage::UnloadWallet(name) => {
+                if let Err(e) = self.unload_wallet(&name) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to unload wallet"),
+                    ));
+                }
+                self.update_wallets();
             }
-            (BitcoinMessage::Connect, _) => {
+            BitcoinMessage::SelectWallet(name) => {
+                if let Err(e) = self.select_wallet(name) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to select wallet"),
+                    ));
+                }
+                self.update_wallets();
+                self.update_data();
+            }
+            BitcoinMessage::UnlockWallet(passphrase) => match self.unlock_wallet(&passphrase) {
+                Ok(_) => self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                    "Wallet {} unlocked for {}s",
+                    self.wallet_name, WALLET_UNLOCK_TIMEOUT
+                ))),
+                Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                    e.report("Fail to unlock wallet"),
+                )),
+            },
+            BitcoinMessage::Connect => {
                 self.handle_connect();
                 self.update_data();
             }
-            (BitcoinMessage::Disconnect, _) => {
+            BitcoinMessage::Disconnect => {
                 if self.is_connected() {
                     self.disconnect()
                 }
             }
-            (BitcoinMessage::GetNewAddress, _) => match self.get_new_address() {
-                Ok(addr) => self.send_to_gui(BitcoinMessage::NewAddress(addr)),
-                Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(format!(
-                    "Fail to get new address: {:?}",
-                    e
-                ))),
+            BitcoinMessage::GetNewAddress => match self.get_new_address() {
+                Ok(addr) => self.send_to_gui(BitcoinMessage::NewAddress(addr.to_string())),
+                Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                    e.report("Fail to get new address"),
+                )),
             },
-            (BitcoinMessage::Generate(blocks), false) => {
-                self.mining_busy = true;
-                if let Err(e) = self.generate(blocks) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(false));
-                } else {
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(true));
+            BitcoinMessage::Generate(blocks) => self.handle_spawn_job(JobKind::Generate(blocks)),
+            BitcoinMessage::GenerateToSelf(blocks) => {
+                self.handle_spawn_job(JobKind::GenerateToSelf(blocks))
+            }
+            BitcoinMessage::Bootstrap(amount) => self.handle_spawn_job(JobKind::Bootstrap(amount)),
+            BitcoinMessage::GenerateToAddress(params) => {
+                self.handle_spawn_job(JobKind::GenerateToAddress(params))
+            }
+            BitcoinMessage::GenerateToDescriptor(params) => {
+                self.handle_spawn_job(JobKind::GenerateToDescriptor(params))
+            }
+            BitcoinMessage::MineUntilHeight(height) => {
+                self.handle_spawn_job(JobKind::MineUntilHeight(height))
+            }
+            BitcoinMessage::MineUntilConfirmed {
+                outpoint,
+                confirmations,
+            } => self.handle_spawn_job(JobKind::MineUntilConfirmed {
+                outpoint,
+                confirmations,
+            }),
+            BitcoinMessage::AdvanceMtp(timestamp) => {
+                if let Err(e) = self.advance_mtp(timestamp) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to advance MTP")));
+                }
+            }
+            BitcoinMessage::SendToAddress(params) => {
+                self.handle_spawn_job(JobKind::SendToAddress(params))
+            }
+            BitcoinMessage::SendToDescriptor(params) => {
+                self.handle_spawn_job(JobKind::SendToDescriptor(params))
+            }
+            BitcoinMessage::CancelJob(id) => self.cancel_job(id),
+            BitcoinMessage::NodeDown { error, retry_in } => {
+                if self.node_healthy {
+                    self.node_healthy = false;
+                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                        "Connection to bitcoind lost: {}",
+                        error
+                    )));
+                    if let Some(sender) = self.auto_block_sender.as_ref() {
+                        if let Err(e) = sender.try_send(AutoBlockMessage::Pause) {
+                            log::error!("Fail to snd message from BitcoinD to miner: {}", e);
+                        }
+                    }
+                }
+                self.send_to_gui(BitcoinMessage::UpdateHealth(NodeHealth::Degraded {
+                    error,
+                    retry_in,
+                }));
+            }
+            // already reconnected
+            BitcoinMessage::NodeUp if self.node_healthy => {}
+            BitcoinMessage::NodeUp => match self.reconnect() {
+                Ok(_) => {
+                    self.node_healthy = true;
+                    if let Some(sender) = self.health_sender.as_ref() {
+                        if let Err(e) = sender.try_send(()) {
+                            log::error!("Fail to snd message from BitcoinD to monitor: {}", e);
+                        }
+                    }
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        "Connection to bitcoind restored".to_string(),
+                    ));
+                    self.send_to_gui(BitcoinMessage::UpdateHealth(NodeHealth::Healthy));
+                    self.update_data();
                 }
+                // the monitor notifies `NodeUp` again until acknowledged
+                Err(e) => self.send_to_gui(BitcoinMessage::SendMessage(
+                    e.report("Fail to reconnect, retrying"),
+                )),
+            },
+            BitcoinMessage::JobDone(id) => {
+                self.jobs.remove(&id);
                 self.update_data();
-                self.mining_busy = false;
             }
-            (BitcoinMessage::GenerateToSelf(blocks), false) => {
-                self.mining_busy = true;
-                if let Err(e) = self.generate_to_self(blocks) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(false));
-                } else {
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(true));
+            BitcoinMessage::BlockMined => {
+                if let Err(e) = self.maybe_send_every_block() {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("maybe_send_every_block()"),
+                    ));
+                }
+                if let Err(e) = self.maybe_send_back() {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("maybe_send_back()")));
+                }
+                if let Err(e) = self.run_actors() {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("run_actors()")));
+                }
+                if let Err(e) = self.maybe_advance_mock_time() {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("maybe_advance_mock_time()"),
+                    ));
                 }
                 self.update_data();
-                self.mining_busy = false;
             }
-            (BitcoinMessage::GenerateToAddress(params), false) => {
-                self.mining_busy = true;
-                if let Err(e) = self.generate_to_address(params) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(false));
+            BitcoinMessage::SetExternalWallet(descriptor) => {
+                if let Err(e) = self.load_external_wallet(&descriptor) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to load external wallet"),
+                    ));
+                    self.send_to_gui(BitcoinMessage::ExternalWalletLoaded(false));
                 } else {
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(true));
+                    self.send_to_gui(BitcoinMessage::ExternalWalletLoaded(true));
                 }
                 self.update_data();
-                self.mining_busy = false;
             }
-            (BitcoinMessage::GenerateToDescriptor(params), false) => {
-                self.mining_busy = true;
-                if let Err(e) = self.generate_to_descriptor(params) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(false));
-                } else {
-                    self.send_to_gui(BitcoinMessage::GenerateResponse(true));
+            BitcoinMessage::ExternalSendToAddress(params) => {
+                let result = self.external_send_to_address(params);
+                self.handle_external_send(result);
+            }
+            BitcoinMessage::ExternalSendToSelf(amount) => {
+                let result = self.external_send_to_self(amount);
+                self.handle_external_send(result);
+            }
+            BitcoinMessage::ExternalSweep => {
+                let result = self.external_sweep();
+                self.handle_external_send(result);
+            }
+            BitcoinMessage::EnableSendBack(params) => {
+                self.send_back = Some(params);
+            }
+            BitcoinMessage::DisableSendBack => {
+                self.send_back = None;
+            }
+            BitcoinMessage::AddActor(params) => {
+                match self.add_actor(params) {
+                    Ok(id) => self
+                        .send_to_gui(BitcoinMessage::SendMessage(format!("Actor #{} added", id))),
+                    Err(e) => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to add actor")))
+                    }
                 }
+                self.update_actors();
                 self.update_data();
-                self.mining_busy = false;
             }
-            (BitcoinMessage::SendToAddress(params), _) => {
-                if let Err(e) = self.send_to_address(params) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::SendResponse(false));
-                } else {
-                    self.send_to_gui(BitcoinMessage::SendResponse(true));
+            BitcoinMessage::RemoveActor(id) => {
+                if let Err(e) = self.remove_actor(id) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to remove actor"),
+                    ));
                 }
+                self.update_actors();
+                self.update_wallets();
+                self.update_data();
             }
-            (BitcoinMessage::SendToDescriptor(params), _) => {
-                if let Err(e) = self.send_to_descriptor(params) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!("{:?}", e)));
-                    self.send_to_gui(BitcoinMessage::SendResponse(false));
-                } else {
-                    self.send_to_gui(BitcoinMessage::SendResponse(true));
+            BitcoinMessage::DecodePsbt(psbt) => match self.decode_psbt(&psbt) {
+                Ok(summary) => {
+                    self.send_to_gui(BitcoinMessage::PsbtDecoded(summary));
+                    self.send_to_gui(BitcoinMessage::PsbtResponse(true));
                 }
+                Err(e) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to decode PSBT")));
+                    self.send_to_gui(BitcoinMessage::PsbtResponse(false));
+                }
+            },
+            BitcoinMessage::SignPsbt(psbt) => {
+                let result = self.sign_psbt(&psbt).map(|(psbt, complete)| {
+                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                        "PSBT signed, complete: {}",
+                        complete
+                    )));
+                    psbt
+                });
+                self.handle_psbt(result);
             }
-            (BitcoinMessage::BlockMined, _) => {
-                if let Err(e) = self.maybe_send_every_block() {
+            BitcoinMessage::CombinePsbt(psbts) => {
+                let result = self.combine_psbt(&psbts);
+                self.handle_psbt(result);
+            }
+            BitcoinMessage::FinalizePsbt { psbt, broadcast } => {
+                match self.finalize_psbt(&psbt, broadcast) {
+                    Ok(txid) if broadcast => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                            "Transaction broadcasted: {}",
+                            txid
+                        )));
+                        self.send_to_gui(BitcoinMessage::PsbtResponse(true));
+                    }
+                    result => self.handle_psbt(result),
+                }
+                self.update_data();
+            }
+            BitcoinMessage::DecodeRawTx(hex) => match Self::decode_raw_tx(&hex, self.network) {
+                Ok(summary) => {
+                    self.send_to_gui(BitcoinMessage::RawTxDecoded(summary));
+                    self.send_to_gui(BitcoinMessage::RawTxResponse(true));
+                }
+                Err(e) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to decode transaction"),
+                    ));
+                    self.send_to_gui(BitcoinMessage::RawTxResponse(false));
+                }
+            },
+            BitcoinMessage::TestRawTx(hex) => match self.test_raw_tx(&hex) {
+                Ok(msg) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(msg));
+                    self.send_to_gui(BitcoinMessage::RawTxResponse(true));
+                }
+                Err(e) => {
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Mempool test failed")));
+                    self.send_to_gui(BitcoinMessage::RawTxResponse(false));
+                }
+            },
+            BitcoinMessage::BroadcastRawTx { hex, mine } => {
+                match self.broadcast_raw_tx(&hex, mine) {
+                    Ok(txid) => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                            "Transaction broadcasted: {}{}",
+                            txid,
+                            if mine { " (mined)" } else { "" }
+                        )));
+                        self.send_to_gui(BitcoinMessage::RawTxResponse(true));
+                    }
+                    Err(e) => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(
+                            e.report("Fail to broadcast transaction"),
+                        ));
+                        self.send_to_gui(BitcoinMessage::RawTxResponse(false));
+                    }
+                }
+                self.update_data();
+            }
+            BitcoinMessage::ListMempool => self.update_mempool(),
+            BitcoinMessage::SetCensorRules(rules) => {
+                if let Err(e) = self.set_censor_rules(rules) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to set censorship rules"),
+                    ));
+                }
+            }
+            BitcoinMessage::BlockCensored { censored, rules } => {
+                if censored > 0 {
                     self.send_to_gui(BitcoinMessage::SendMessage(format!(
-                        "maybe_send_every_block(): {:?}",
-                        e
+                        "Autoblock: {} transaction(s) censored",
+                        censored
                     )));
                 }
+                self.send_to_gui(BitcoinMessage::CensorRules(rules));
+            }
+            BitcoinMessage::BuildBlock(params) => {
+                match self.build_block(&params) {
+                    Ok(hash) => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                            "Block {} mined w/ {} transaction(s)",
+                            hash,
+                            params.txids.len() + params.raw_txs.len()
+                        )));
+                        self.send_to_gui(BitcoinMessage::BlockBuilderResponse(true));
+                    }
+                    Err(e) => {
+                        self.send_to_gui(BitcoinMessage::SendMessage(
+                            e.report("Fail to build block"),
+                        ));
+                        self.send_to_gui(BitcoinMessage::BlockBuilderResponse(false));
+                    }
+                }
+                self.update_mempool();
                 self.update_data();
             }
-            (BitcoinMessage::MinerStopped, _) => {
+            BitcoinMessage::SetMockTime(timestamp) => {
+                if let Err(e) = self.set_mock_time(timestamp) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to set mocktime"),
+                    ));
+                }
+            }
+            BitcoinMessage::AdvanceMockTime(seconds) => {
+                if let Err(e) = self.advance_mock_time(seconds) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to advance mocktime"),
+                    ));
+                }
+            }
+            BitcoinMessage::SetMockTimeStep(step) => {
+                self.mock_time_step = step;
+            }
+            BitcoinMessage::NextBlockIn(delay) => {
+                self.send_to_gui(BitcoinMessage::NextBlockIn(delay));
+            }
+            BitcoinMessage::IncrementAutoblockDescriptorIndex => {
+                self.send_to_gui(BitcoinMessage::IncrementAutoblockDescriptorIndex);
+            }
+            BitcoinMessage::FailMineBlock(report) => {
+                self.send_to_gui(BitcoinMessage::SendMessage(report));
+            }
+            BitcoinMessage::MinerStopped => {
                 self.auto_block_sender = None;
                 self.send_to_gui(BitcoinMessage::MinerStopped);
             }
-            (BitcoinMessage::BatchSent, _) => {
+            BitcoinMessage::BatchSent => {
                 self.update_data();
             }
-            (BitcoinMessage::EnableSendEveryBlock(params), _) => {
+            BitcoinMessage::EnableSendEveryBlock(params) => {
                 self.send_every_block = Some(params);
             }
-            (BitcoinMessage::DisableSendEveryBlock, _) => {
+            BitcoinMessage::AddSchedule(schedule) => {
+                if let Err(e) = self.add_schedule(schedule) {
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to add scheduled send"),
+                    ));
+                }
+                self.update_schedules();
+            }
+            BitcoinMessage::RemoveSchedule(id) => {
+                self.remove_schedule(id);
+                self.update_schedules();
+            }
+            BitcoinMessage::ScheduleTick => {
+                if let Err(e) = self.run_schedules() {
+                    log::debug!("run_schedules() -> {}", e);
+                }
+            }
+            BitcoinMessage::SchedulesSent {
+                height,
+                time,
+                attempts,
+            } => {
+                self.sending_schedules = false;
+                if !attempts.is_empty() {
+                    // a schedule removed meanwhile is not updated
+                    for (id, sent) in attempts {
+                        if let Some(schedule) = self.schedules.get_mut(&id) {
+                            schedule.attempted(height, time, sent);
+                        }
+                    }
+                    self.update_schedules();
+                    self.update_data();
+                }
+            }
+            BitcoinMessage::ActorsPlayed(actors) => {
+                self.playing_actors = false;
+                for actor in actors {
+                    if let Some(registered) = self.actors.get_mut(&actor.id) {
+                        *registered = actor;
+                    }
+                }
+                self.send_to_gui(BitcoinMessage::Actors(
+                    self.actors.values().cloned().collect(),
+                ));
+                self.update_data();
+            }
+            BitcoinMessage::DisableSendEveryBlock => {
                 self.send_every_block = None;
             }
-            (BitcoinMessage::StartAutoBlock(delay), _) => {
+            BitcoinMessage::StartAutoBlock(params) => {
                 log::info!("start auto block");
-                if let Err(e) = self.start_auto_block(delay) {
+                if let Err(e) = self.start_auto_block(params) {
                     self.send_to_gui(BitcoinMessage::MinerStopped);
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
-                        "Fail to start autoblock: {:?}",
-                        e
-                    )));
+                    self.send_to_gui(BitcoinMessage::SendMessage(
+                        e.report("Fail to start autoblock"),
+                    ));
                 }
             }
-            (BitcoinMessage::StopAutoBlock, _) => {
+            BitcoinMessage::StopAutoBlock => {
                 self.stop_auto_block();
             }
-            (BitcoinMessage::Invalidate(blocks), _) => {
+            BitcoinMessage::Invalidate(blocks) => {
                 if let Err(e) = self.invalidate_blocks(blocks) {
-                    self.send_to_gui(BitcoinMessage::SendMessage(format!(
-                        "Fail to reorg: {:?}",
-                        e
-                    )));
+                    self.send_to_gui(BitcoinMessage::SendMessage(e.report("Fail to reorg")));
                 }
             }
 
@@ -598,64 +2812,216 @@ impl BitcoinD {
         }
     }
 
-    pub fn start_auto_block(&mut self, delay_ms: Duration) -> Result<(), Error> {
-        log::info!("BitcoinD.start_auto_block({:?})", delay_ms);
+    fn handle_external_send(&self, result: Result<(), Error>) {
+        if let Err(e) = result {
+            self.send_to_gui(BitcoinMessage::SendMessage(
+                e.report("Fail to send from external wallet"),
+            ));
+            self.send_to_gui(BitcoinMessage::ExternalSendResponse(false));
+        } else {
+            self.send_to_gui(BitcoinMessage::ExternalSendResponse(true));
+        }
+        self.update_data();
+    }
+
+    /// Returns the address the `index`-th block mined by the autoblock miner should pay to.
+    pub fn payout_address<R: Rng>(
+        rng: &mut R,
+        secp: &miniscript::bitcoin::secp256k1::Secp256k1<All>,
+        wallet_client: &Client,
+        payout: &PayoutTarget,
+        index: u32,
+        network: Network,
+        mix: &AddressMix,
+    ) -> Result<Address, Error> {
+        match payout {
+            PayoutTarget::Random => Ok(Self::get_random_address(rng, secp, network, mix)),
+            PayoutTarget::ToSelf => Self::new_wallet_address(rng, wallet_client, mix),
+            PayoutTarget::Address(address) => Ok(address.clone()),
+            PayoutTarget::Descriptor {
+                descriptor,
+                start_index,
+            } => Self::address_from_descriptor(
+                secp,
+                descriptor.clone(),
+                start_index + index,
+                network,
+            ),
+            PayoutTarget::RoundRobin(addresses) => addresses
+                .get(index as usize % addresses.len().max(1))
+                .cloned()
+                .ok_or(Error::EmptyAddressList),
+        }
+    }
+
+    /// Derive the scripts of the descriptor rules, here rather than in the GUI as it
+    /// takes a while.
+    fn derive_censor_rules(&self, rules: &mut [CensorRule]) -> Result<(), Error> {
+        for rule in rules {
+            rule.criteria.derive(&self.secp)?;
+        }
+        Ok(())
+    }
+
+    /// Censorship rules need `generateblock`, so they are regtest only.
+    fn check_can_censor(&self, rules: &[CensorRule]) -> Result<(), Error> {
+        if !rules.is_empty() && self.network != Network::Regtest {
+            Err(Error::MiningUnavailable(self.network))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn set_censor_rules(&self, mut rules: Vec<CensorRule>) -> Result<(), Error> {
+        self.check_can_censor(&rules)?;
+        self.derive_censor_rules(&mut rules)?;
+        if let Some(sender) = self.auto_block_sender.as_ref() {
+            if let Err(e) = sender.try_send(AutoBlockMessage::SetCensorRules(rules)) {
+                log::error!("Fail to snd message to miner: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn start_auto_block(&mut self, mut params: AutoBlock) -> Result<(), Error> {
+        log::info!("BitcoinD.start_auto_block({:?})", params);
+        let backend = self.backend()?;
+        self.check_can_censor(&params.censor)?;
+        self.derive_censor_rules(&mut params.censor)?;
         if self.is_connected() {
-            let (sender, receiver) = std::sync::mpsc::channel();
+            if let PayoutTarget::RoundRobin(addresses) = &params.payout {
+                if addresses.is_empty() {
+                    return Err(Error::EmptyAddressList);
+                }
+            }
+            let (sender, receiver) = async_channel::unbounded();
             self.auto_block_sender = Some(sender);
             let sender = self.loopback.clone();
-            let (client, _) = self.connect()?;
+            let (client, wallet_client) = self.connect()?;
+            let network = self.network;
+            let seed = traffic::derive_seed(self.seed, SeedStream::AutoBlock, self.auto_block_runs);
+            let interval_seed =
+                traffic::derive_seed(self.seed, SeedStream::BlockInterval, self.auto_block_runs);
+            self.auto_block_runs += 1;
+            self.send_to_gui(BitcoinMessage::SendMessage(format!(
+                "Autoblock started, seed {}, address types {}",
+                seed, self.address_mix
+            )));
+            let address_mix = self.address_mix.clone();
+            let AutoBlock {
+                interval,
+                payout,
+                mut censor,
+            } = params;
 
             tokio::spawn(async move {
                 log::info!("Spawn miner thread");
-                let mut last_block = time::Instant::now();
-                let mut stop = false;
+                let notify = |msg: BitcoinMessage| {
+                    if let Err(e) = sender.try_send(msg) {
+                        log::error!("Fail to snd message from miner to BitcoinD: {}", e);
+                    }
+                };
                 let secp = miniscript::bitcoin::secp256k1::Secp256k1::new();
+                // only advanced when a block is mined
+                let mut rng = StdRng::seed_from_u64(seed);
+                let now = tokio::time::Instant::now();
+                let mut clock =
+                    BlockClock::new(interval, StdRng::seed_from_u64(interval_seed), now);
+                let mut mined = 0;
+                let (mut client, mut wallet_client) = (client, wallet_client);
+                if let Some(delay) = clock.remaining(now) {
+                    notify(BitcoinMessage::NextBlockIn(delay));
+                }
 
-                while !stop {
-                    #[allow(clippy::collapsible_match)]
-                    if let Ok(msg) = receiver.try_recv() {
+                loop {
+                    let msg = match clock.deadline() {
+                        // paused, wait for the service only
+                        None => Some(receiver.recv().await),
+                        Some(deadline) => tokio::select! {
+                            msg = receiver.recv() => Some(msg),
+                            _ = tokio::time::sleep_until(deadline) => None,
+                        },
+                    };
+
+                    if let Some(msg) = msg {
                         log::info!("Miner rcv msg: {:?}", msg);
-                        #[allow(irrefutable_let_patterns)]
-                        if let AutoBlockMessage::Stop = msg {
-                            stop = true;
-                            continue;
+                        let msg = match msg {
+                            Ok(msg) => msg,
+                            Err(_) => break,
+                        };
+                        let paused = clock.deadline().is_none();
+                        let now = tokio::time::Instant::now();
+                        clock.on_message(&msg, now);
+                        match msg {
+                            AutoBlockMessage::Stop => break,
+                            AutoBlockMessage::Pause => {}
+                            AutoBlockMessage::Resume {
+                                client: c,
+                                wallet_client: w,
+                            }
+                            | AutoBlockMessage::UpdateClients {
+                                client: c,
+                                wallet_client: w,
+                            } => (client, wallet_client) = (c, w),
+                            AutoBlockMessage::SetCensorRules(rules) => censor = rules,
+                        }
+                        if let (true, Some(remaining)) = (paused, clock.remaining(now)) {
+                            notify(BitcoinMessage::NextBlockIn(remaining));
+                        }
+                        continue;
+                    }
+
+                    log::info!("Miner: mine a block");
+                    let now = tokio::time::Instant::now();
+                    let mut draw = rng.clone();
+
+                    let result = rpc_blocking(|| {
+                        let address = Self::payout_address(
+                            &mut draw,
+                            &secp,
+                            &wallet_client,
+                            &payout,
+                            mined,
+                            network,
+                            &address_mix,
+                        )?;
+                        if censor.is_empty() {
+                            backend.mine(&client, &secp, &address).map(|_| 0)
+                        } else {
+                            Self::mine_censored(&client, &address, &censor)
                         }
+                    });
+                    clock.mined(now);
+                    if let Some(remaining) = clock.remaining(tokio::time::Instant::now()) {
+                        notify(BitcoinMessage::NextBlockIn(remaining));
                     }
-                    let now = time::Instant::now();
-                    if now > last_block + delay_ms {
-                        log::info!("Miner: mine a block");
-                        last_block = now;
-
-                        let address = Self::get_random_address(&secp);
-                        match client.generate_to_address(1, &address) {
-                            Ok(_) => {
-                                if let Err(e) = sender.send(BitcoinMessage::BlockMined) {
-                                    log::error!(
-                                        "Fail to snd message from miner to BitcoinD: {}",
-                                        e
-                                    );
+                    match result {
+                        Ok(censored) => {
+                            rng = draw;
+                            mined += 1;
+                            if !censor.is_empty() {
+                                for rule in censor.iter_mut() {
+                                    rule.blocks = rule.blocks.saturating_sub(1);
                                 }
+                                censor.retain(|rule| rule.blocks > 0);
+                                notify(BitcoinMessage::BlockCensored {
+                                    censored,
+                                    rules: censor.clone(),
+                                });
                             }
-                            Err(e) => {
-                                if let Err(e) =
-                                    sender.send(BitcoinMessage::FailMineBlock(format!("{:?}", e)))
-                                {
-                                    log::error!(
-                                        "Fail to snd message from miner to BitcoinD: {}",
-                                        e
-                                    );
-                                }
+                            if let PayoutTarget::Descriptor { .. } = payout {
+                                notify(BitcoinMessage::IncrementAutoblockDescriptorIndex);
                             }
+                            notify(BitcoinMessage::BlockMined);
                         }
+                        Err(e) => notify(BitcoinMessage::FailMineBlock(
+                            e.report("Miner fail to mine a block"),
+                        )),
                     }
-                    tokio::time::sleep(Duration::from_nanos(20)).await;
                 }
 
                 log::info!("Miner stopped");
-                if let Err(e) = sender.send(BitcoinMessage::MinerStopped) {
-                    log::error!("Fail to snd message from miner to BitcoinD: {}", e);
-                }
+                notify(BitcoinMessage::MinerStopped);
             });
         }
 
@@ -664,27 +3030,31 @@ impl BitcoinD {
 
     pub fn stop_auto_block(&self) {
         if let Some(sender) = self.auto_block_sender.as_ref() {
-            if let Err(e) = sender.send(AutoBlockMessage::Stop) {
+            if let Err(e) = sender.try_send(AutoBlockMessage::Stop) {
                 log::error!("Fail to snd message from miner to miner: {}", e);
             }
         }
     }
 
     pub fn send_to_gui(&self, message: BitcoinMessage) {
-        let sender = self.sender.clone();
-        tokio::spawn(async move {
-            if sender.send(message).await.is_err() {
-                log::debug!("send_to_gui() -> Fail to send Message")
-            };
-        });
+        // the channel is unbounded, try_send() only fail if it's closed
+        if self.sender.try_send(message).is_err() {
+            log::debug!("send_to_gui() -> Fail to send Message")
+        }
     }
 
     pub fn update_data(&self) {
         if let Ok(blocks) = self.get_block_height() {
             self.send_to_gui(BitcoinMessage::UpdateBlockchainTip(blocks))
         }
-        if let Ok(balance) = self.get_balance() {
-            self.send_to_gui(BitcoinMessage::UpdateBalance(balance))
+        if let Ok(time) = self.get_median_time() {
+            self.send_to_gui(BitcoinMessage::UpdateMedianTime(time))
+        }
+        if let Ok(balances) = self.get_balances() {
+            self.send_to_gui(BitcoinMessage::UpdateBalance(balances))
+        }
+        if let Ok(balance) = self.get_external_balance() {
+            self.send_to_gui(BitcoinMessage::UpdateExternalBalance(balance))
         }
     }
 
@@ -696,31 +3066,53 @@ impl BitcoinD {
 impl ServiceFn<BitcoinMessage> for BitcoinD {
     fn new(
         sender: async_channel::Sender<BitcoinMessage>,
-        receiver: std::sync::mpsc::Receiver<BitcoinMessage>,
-        loopback: std::sync::mpsc::Sender<BitcoinMessage>,
+        receiver: async_channel::Receiver<BitcoinMessage>,
+        loopback: async_channel::Sender<BitcoinMessage>,
     ) -> Self {
+        let seed = rand::thread_rng().gen();
         BitcoinD {
             sender,
             receiver,
             loopback,
             client: None,
             wallet_client: None,
+            wallet_name: DEFAULT_WALLET_NAME.to_string(),
+            network: Network::Regtest,
+            signet_key: None,
+            external_client: None,
             address: None,
             auth: None,
-            mining_busy: false,
+            jobs: BTreeMap::new(),
+            next_job_id: 0,
             secp: miniscript::bitcoin::secp256k1::Secp256k1::new(),
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            seed_jobs: 0,
+            auto_block_runs: 0,
+            address_mix: AddressMix::default(),
             send_every_block: None,
+            schedules: BTreeMap::new(),
+            next_schedule_id: 0,
+            scheduler_sender: None,
+            sending_schedules: false,
+            actors: BTreeMap::new(),
+            actor_clients: BTreeMap::new(),
+            playing_actors: false,
+            next_actor_id: 0,
+            send_back: None,
+            mock_time: None,
+            mock_time_step: None,
             auto_block_sender: None,
+            health_sender: None,
+            node_healthy: true,
         }
     }
 
     async fn run(&mut self) {
-        loop {
-            if let Ok(msg) = self.receiver.try_recv() {
-                self.handle_message(msg);
-            }
-            tokio::time::sleep(Duration::from_nanos(20)).await;
+        while let Ok(msg) = self.receiver.recv().await {
+            rpc_blocking(|| self.handle_message(msg));
         }
+        log::info!("BitcoinD: channel closed, stop service");
     }
 }
 
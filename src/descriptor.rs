@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use miniscript::{Descriptor, DescriptorPublicKey};
+
+use crate::error::Error;
+
+/// Fragments whose arguments are keys, w/ the index of their first key argument.
+const KEY_FRAGMENTS: [(&str, usize); 11] = [
+    ("pk", 0),
+    ("pkh", 0),
+    ("wpkh", 0),
+    ("combo", 0),
+    ("pk_k", 0),
+    ("pk_h", 0),
+    ("multi", 1),
+    ("sortedmulti", 1),
+    ("multi_a", 1),
+    ("sortedmulti_a", 1),
+    ("rawtr", 0),
+];
+
+/// Parse a public descriptor, the error locates the invalid part of `descriptor`.
+pub fn parse_descriptor(descriptor: &str) -> Result<Descriptor<DescriptorPublicKey>, Error> {
+    Descriptor::<DescriptorPublicKey>::from_str(descriptor).map_err(|e| Error::ParseDescriptor {
+        message: e.to_string(),
+        position: locate_error(descriptor),
+    })
+}
+
+/// Whether the argument `arg` of `fragment` is a key.
+fn is_key(fragment: &str, arg: usize) -> bool {
+    // strip the wrappers (`v:pk(..)`)
+    let fragment = fragment.rsplit(':').next().unwrap_or(fragment);
+    match fragment {
+        // the internal key, the script tree is made of fragments
+        "tr" => arg == 0,
+        _ => KEY_FRAGMENTS
+            .iter()
+            .any(|(name, first)| *name == fragment && arg >= *first),
+    }
+}
+
+/// Char offset of the first error in `descriptor`: a char out of the descriptor charset,
+/// an unbalanced bracket, a key miniscript fails to parse or, the expression being
+/// valid, the checksum. `None` if the expression looks valid, miniscript then rejects
+/// its semantics (e.g. a fragment type).
+fn locate_error(descriptor: &str) -> Option<usize> {
+    // (fragment, current argument), `{` for the taproot tree branches
+    let mut stack: Vec<(&str, usize)> = Vec::new();
+    let mut token_start = 0;
+    // start of the key origin (`[fingerprint/path]`) being read
+    let mut origin = None;
+    let mut checksum = None;
+    for (i, c) in descriptor.char_indices() {
+        if !c.is_ascii() || c.is_ascii_control() {
+            return Some(i);
+        }
+        let token = &descriptor[token_start..i];
+        match c {
+            '[' if origin.is_none() && token.is_empty() => origin = Some(i),
+            ']' if origin.is_some() => origin = None,
+            '[' | ']' => return Some(i),
+            _ if origin.is_some() => {}
+            '(' => {
+                stack.push((token, 0));
+                token_start = i + 1;
+            }
+            '{' if token.is_empty() => {
+                stack.push(("{", 0));
+                token_start = i + 1;
+            }
+            ',' | ')' | '}' => {
+                let Some((fragment, arg)) = stack.last_mut() else {
+                    return Some(i);
+                };
+                if is_key(fragment, *arg) && DescriptorPublicKey::from_str(token).is_err() {
+                    return Some(token_start);
+                }
+                match c {
+                    ',' => *arg += 1,
+                    ')' if *fragment != "{" => {
+                        stack.pop();
+                    }
+                    '}' if *fragment == "{" => {
+                        stack.pop();
+                    }
+                    _ => return Some(i),
+                }
+                token_start = i + 1;
+            }
+            '#' => {
+                checksum = Some(i);
+                break;
+            }
+            _ if stack.is_empty() && token_start > 0 => return Some(i),
+            _ => {}
+        }
+    }
+    if origin.is_some() {
+        return origin;
+    }
+    if !stack.is_empty() {
+        return Some(checksum.unwrap_or(descriptor.len()));
+    }
+    match checksum {
+        Some(i) if Descriptor::<DescriptorPublicKey>::from_str(&descriptor[..i]).is_ok() => {
+            Some(i + 1)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TPUB: &str = "tpubDEUUVSJyh6t12FbNhmmYa1M39AiD2VKGBaGT54aPz2xVF5Kg1dx3XSb5T4nKBakEz8ypy35fYVAZgBc7MVwQ2qEZEZRqDbvDu8w5AZVu4q2";
+
+    fn position(descriptor: &str) -> Option<usize> {
+        match parse_descriptor(descriptor) {
+            Err(Error::ParseDescriptor { position, .. }) => position,
+            other => panic!("{}: {:?}", descriptor, other),
+        }
+    }
+
+    #[test]
+    fn descriptor_error_position() {
+        let key = format!("[9c32dc88/48'/1'/0'/2']{}/0/*", TPUB);
+        assert!(parse_descriptor(&format!("wpkh({})", key)).is_ok());
+
+        // the base58 checksum of the key fails
+        let bad = TPUB.replacen("tpubDEUU", "tpubDEUV", 1);
+        let descriptor = format!("wsh(multi(1,{},{}/0/*))", key, bad);
+        assert_eq!(position(&descriptor), descriptor.find(&bad));
+        // not a key
+        assert_eq!(position("wpkh(foo)"), Some(5));
+        assert_eq!(position("wpkh()"), Some(5));
+        let descriptor = format!("wsh(and_v(v:pk({}),older(foo)))", bad);
+        assert_eq!(position(&descriptor), Some(15));
+
+        // unbalanced
+        assert_eq!(position(&format!("wpkh({}", key)), Some(key.len() + 5));
+        assert_eq!(position(&format!("wpkh({}))", key)), Some(key.len() + 6));
+        assert_eq!(position(&format!("wpkh({}}}", key)), Some(key.len() + 5));
+        assert_eq!(position(&format!("wpkh({})x", key)), Some(key.len() + 6));
+        assert_eq!(position(&format!("wpkh([9c32dc88{}/0/*)", TPUB)), Some(5));
+        // out of the charset
+        assert_eq!(position(&format!("wpkh(\u{e9}{})", key)), Some(5));
+
+        // only the checksum is wrong
+        let descriptor = format!("wpkh({})#00000000", key);
+        assert_eq!(position(&descriptor), descriptor.find('#').map(|i| i + 1));
+    }
+}
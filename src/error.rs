@@ -0,0 +1,292 @@
+use std::fmt::{self, Display, Formatter};
+
+use bitcoincore_rpc::jsonrpc::{self, error::RpcError};
+use miniscript::bitcoin::Network;
+
+// bitcoind RPC error codes, see bitcoin/src/rpc/protocol.h
+const RPC_TYPE_ERROR: i32 = -3;
+const RPC_WALLET_ERROR: i32 = -4;
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+const RPC_WALLET_INSUFFICIENT_FUNDS: i32 = -6;
+const RPC_INVALID_PARAMETER: i32 = -8;
+const RPC_WALLET_UNLOCK_NEEDED: i32 = -13;
+const RPC_WALLET_NOT_FOUND: i32 = -18;
+const RPC_WALLET_NOT_SPECIFIED: i32 = -19;
+const RPC_VERIFY_ERROR: i32 = -25;
+const RPC_VERIFY_REJECTED: i32 = -26;
+const RPC_VERIFY_ALREADY_IN_CHAIN: i32 = -27;
+const RPC_IN_WARMUP: i32 = -28;
+const RPC_WALLET_ALREADY_LOADED: i32 = -35;
+
+#[derive(Debug)]
+pub enum Error {
+    CredentialMissing,
+    NotConnected,
+    ParseDescriptor {
+        message: String,
+        /// Char offset of the error in the descriptor
+        position: Option<usize>,
+    },
+    DeriveDescriptor,
+    EmptyAddressList,
+    ExternalWalletMissing,
+    ImportDescriptor(String),
+    PsbtNotComplete,
+    DecodeTx(String),
+    TxRejected(String),
+    UtxoNotFound,
+    MtpNotReached,
+    Cancelled,
+    InsufficientFunds(String),
+    WalletLocked,
+    WalletNotFound(String),
+    WalletAlreadyLoaded(String),
+    InvalidAddress(String),
+    AddressNetwork {
+        address: String,
+        expected: Network,
+    },
+    InvalidParameter(String),
+    Warmup(String),
+    /// No mining backend usable on this network
+    MiningUnavailable(Network),
+    /// `submitblock` rejected the block
+    BlockRejected(String),
+    /// Fail to build or sign a signet block
+    Signet(String),
+    /// RPC error w/o dedicated variant
+    RpcCode {
+        code: i32,
+        message: String,
+    },
+    /// Transport/serialization errors
+    Rpc(bitcoincore_rpc::Error),
+}
+
+impl Error {
+    /// Remediation hint to display along the error.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Error::CredentialMissing => Some("fill the RPC credentials or the cookie path"),
+            Error::NotConnected => Some("connect to bitcoind first"),
+            Error::ParseDescriptor { .. } => {
+                Some("check the descriptor syntax, keys must be xpubs w/ a derivation path")
+            }
+            Error::DeriveDescriptor => Some("use a ranged descriptor (ending with /*)"),
+            Error::ExternalWalletMissing => Some("load an external wallet descriptor first"),
+            Error::PsbtNotComplete => Some("sign the PSBT w/ all the required keys first"),
+            Error::UtxoNotFound => Some("the outpoint is unknown or already spent"),
+            Error::MtpNotReached => Some("retry, or set a mocktime further in the future"),
+            Error::InsufficientFunds(_) => {
+                Some("generate some blocks to self, coinbases mature after 100 blocks")
+            }
+            Error::WalletLocked => Some("unlock the wallet w/ walletpassphrase"),
+            Error::WalletNotFound(_) => Some("the wallet is not loaded, reconnect to load it"),
+            Error::AddressNetwork { .. } => Some("use an address of the node network"),
+            Error::Warmup(_) => Some("bitcoind is starting, retry in a few seconds"),
+            Error::MiningUnavailable(Network::Signet) => {
+                Some("set the signet challenge key to sign blocks (custom blocks are regtest only)")
+            }
+            Error::MiningUnavailable(_) => Some("use a regtest or signet node to generate blocks"),
+            Error::BlockRejected(_) => Some("check the signet challenge key and the node logs"),
+            Error::Rpc(bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(_))) => {
+                Some("check bitcoind is running and the RPC address/credentials")
+            }
+            _ => None,
+        }
+    }
+
+    /// Human readable report of the error, prefixed by `context`, w/ the hint if any.
+    pub fn report(&self, context: &str) -> String {
+        let mut report = format!("{}: {}", context, self);
+        if let Some(hint) = self.hint() {
+            report.push_str(&format!("\n    hint: {}", hint));
+        }
+        report
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CredentialMissing => write!(f, "RPC credentials missing"),
+            Error::NotConnected => write!(f, "not connected to bitcoind"),
+            Error::ParseDescriptor {
+                message,
+                position: Some(position),
+            } => write!(f, "invalid descriptor (at char {}): {}", position, message),
+            Error::ParseDescriptor { message, .. } => write!(f, "invalid descriptor: {}", message),
+            Error::DeriveDescriptor => write!(f, "fail to derive an address from the descriptor"),
+            Error::EmptyAddressList => write!(f, "the address list is empty"),
+            Error::ExternalWalletMissing => write!(f, "no external wallet loaded"),
+            Error::ImportDescriptor(e) => write!(f, "fail to import descriptor: {}", e),
+            Error::PsbtNotComplete => write!(f, "the PSBT is not complete"),
+            Error::DecodeTx(e) => write!(f, "fail to decode transaction: {}", e),
+            Error::TxRejected(e) => write!(f, "transaction rejected: {}", e),
+            Error::UtxoNotFound => write!(f, "utxo not found"),
+            Error::MtpNotReached => write!(f, "median time past not reached"),
+            Error::Cancelled => write!(f, "cancelled"),
+            Error::InsufficientFunds(e) => write!(f, "insufficient funds: {}", e),
+            Error::WalletLocked => write!(f, "the wallet is locked"),
+            Error::WalletNotFound(e) => write!(f, "wallet not found: {}", e),
+            Error::WalletAlreadyLoaded(e) => write!(f, "wallet already loaded: {}", e),
+            Error::InvalidAddress(e) => write!(f, "invalid address {}", e),
+            Error::AddressNetwork { address, expected } => {
+                write!(f, "address {} is not a {} address", address, expected)
+            }
+            Error::InvalidParameter(e) => write!(f, "invalid parameter: {}", e),
+            Error::Warmup(e) => write!(f, "bitcoind warming up: {}", e),
+            Error::MiningUnavailable(network) => {
+                write!(f, "block generation is not available on {}", network)
+            }
+            Error::BlockRejected(e) => write!(f, "block rejected: {}", e),
+            Error::Signet(e) => write!(f, "signet error: {}", e),
+            Error::RpcCode { code, message } => write!(f, "RPC error {}: {}", code, message),
+            Error::Rpc(e) => write!(f, "RPC error: {}", e),
+        }
+    }
+}
+
+impl From<bitcoincore_rpc::Error> for Error {
+    fn from(value: bitcoincore_rpc::Error) -> Self {
+        match value {
+            bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(RpcError {
+                code,
+                message,
+                ..
+            })) => match code {
+                RPC_WALLET_INSUFFICIENT_FUNDS => Error::InsufficientFunds(message),
+                RPC_WALLET_ERROR if message.contains("Insufficient funds") => {
+                    Error::InsufficientFunds(message)
+                }
+                RPC_WALLET_UNLOCK_NEEDED => Error::WalletLocked,
+                RPC_WALLET_NOT_FOUND | RPC_WALLET_NOT_SPECIFIED => Error::WalletNotFound(message),
+                RPC_WALLET_ALREADY_LOADED => Error::WalletAlreadyLoaded(message),
+                RPC_INVALID_ADDRESS_OR_KEY => Error::InvalidAddress(message),
+                RPC_INVALID_PARAMETER | RPC_TYPE_ERROR => Error::InvalidParameter(message),
+                RPC_VERIFY_ERROR | RPC_VERIFY_REJECTED | RPC_VERIFY_ALREADY_IN_CHAIN => {
+                    Error::TxRejected(format!("{} (code {})", message, code))
+                }
+                RPC_IN_WARMUP => Error::Warmup(message),
+                code => Error::RpcCode { code, message },
+            },
+            e => Error::Rpc(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_error(code: i32, message: &str) -> bitcoincore_rpc::Error {
+        bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Rpc(RpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        }))
+    }
+
+    #[test]
+    fn rpc_error_codes() {
+        let from = |code, message| Error::from(rpc_error(code, message));
+        assert!(matches!(
+            from(-6, "Insufficient funds"),
+            Error::InsufficientFunds(_)
+        ));
+        assert!(matches!(
+            from(-4, "Insufficient funds"),
+            Error::InsufficientFunds(_)
+        ));
+        assert!(matches!(
+            from(-4, "Wallet file verification failed"),
+            Error::RpcCode { code: -4, .. }
+        ));
+        assert!(matches!(
+            from(-13, "Please enter the passphrase"),
+            Error::WalletLocked
+        ));
+        assert!(matches!(
+            from(-18, "Requested wallet does not exist"),
+            Error::WalletNotFound(_)
+        ));
+        assert!(matches!(
+            from(-19, "Wallet file not specified"),
+            Error::WalletNotFound(_)
+        ));
+        assert!(matches!(
+            from(-35, "Wallet is already loaded"),
+            Error::WalletAlreadyLoaded(_)
+        ));
+        assert!(matches!(
+            from(-5, "Invalid address"),
+            Error::InvalidAddress(_)
+        ));
+        assert!(matches!(
+            from(-8, "Invalid parameter"),
+            Error::InvalidParameter(_)
+        ));
+        assert!(matches!(
+            from(-3, "Expected type number"),
+            Error::InvalidParameter(_)
+        ));
+        for code in [-25, -26, -27] {
+            match from(code, "bad-txns") {
+                Error::TxRejected(message) => assert!(message.contains(&code.to_string())),
+                e => panic!("code {}: {:?}", code, e),
+            }
+        }
+        assert!(matches!(from(-28, "Loading block index"), Error::Warmup(_)));
+        assert!(matches!(from(-1, "help"), Error::RpcCode { code: -1, .. }));
+        assert!(matches!(
+            Error::from(bitcoincore_rpc::Error::ReturnedError("error".to_string())),
+            Error::Rpc(_)
+        ));
+    }
+
+    #[test]
+    fn hints() {
+        let transport = Error::from(bitcoincore_rpc::Error::JsonRpc(jsonrpc::Error::Transport(
+            "connection refused".into(),
+        )));
+        assert!(transport.hint().is_some());
+        assert!(Error::from(rpc_error(-6, "Insufficient funds"))
+            .hint()
+            .is_some());
+        assert!(Error::from(rpc_error(-13, "locked")).hint().is_some());
+        assert!(Error::from(rpc_error(-18, "not found")).hint().is_some());
+        assert!(Error::from(rpc_error(-28, "warming up")).hint().is_some());
+        assert!(Error::MiningUnavailable(Network::Signet)
+            .hint()
+            .unwrap()
+            .contains("signet challenge key"));
+        assert_ne!(
+            Error::MiningUnavailable(Network::Signet).hint(),
+            Error::MiningUnavailable(Network::Bitcoin).hint()
+        );
+        // w/o hint
+        assert!(Error::from(rpc_error(-1, "help")).hint().is_none());
+        assert!(Error::Cancelled.hint().is_none());
+
+        let report = Error::WalletLocked.report("Fail to send");
+        assert_eq!(
+            report,
+            "Fail to send: the wallet is locked\n    hint: unlock the wallet w/ walletpassphrase"
+        );
+        assert_eq!(Error::Cancelled.report("Job"), "Job: cancelled");
+    }
+
+    #[test]
+    fn descriptor_error() {
+        let error = |position| Error::ParseDescriptor {
+            message: "bad key".to_string(),
+            position,
+        };
+        assert_eq!(
+            error(Some(5)).to_string(),
+            "invalid descriptor (at char 5): bad key"
+        );
+        assert_eq!(error(None).to_string(), "invalid descriptor: bad key");
+        assert!(error(None).hint().is_some());
+    }
+}
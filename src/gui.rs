@@ -4,17 +4,15 @@ use iced::{
     widget::{
         focus_next, focus_previous, scrollable,
         text_editor::{Action, Content, Edit},
-        Button, Checkbox, Column, Container, PickList, Row, Rule, Space, Text, TextEditor,
-        TextInput,
+        Button, Checkbox, Column, Container, PickList, ProgressBar, Row, Rule, Space, Text,
+        TextEditor, TextInput,
     },
     Application, Command, Element, Length, Subscription, Theme,
 };
-use miniscript::{
-    bitcoin::{Address, Amount, Denomination},
-    Descriptor, DescriptorPublicKey,
-};
+use miniscript::bitcoin::{Address, Amount, Denomination, Network, OutPoint, Txid};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::{BTreeMap, BTreeSet},
     env,
     fmt::{self, Display, Formatter},
     fs,
@@ -23,9 +21,18 @@ use std::{
     sync::Arc,
 };
 
-use crate::bitcoind::{
-    self, BitcoinMessage, BitcoindListener, GenerateToAddress, GenerateToDescriptor,
-    SendEveryBlock, SendToAddress, SendToDescriptor,
+use crate::{
+    actor::{self, Actor, ActorId, NewActor},
+    bitcoind::{
+        self, AutoBlock, Balances, BitcoinMessage, BitcoindListener, BuildBlock, GenerateToAddress,
+        GenerateToDescriptor, JobId, JobInfo, JobKind, JobStatus, MempoolTx, NodeHealth, SendBack,
+        SendEveryBlock, SendToAddress, SendToDescriptor,
+    },
+    descriptor::parse_descriptor,
+    error::Error,
+    miner::{BlockInterval, CensorCriteria, CensorRule, PayoutTarget},
+    scheduler::{Frequency, Schedule, ScheduleId, ScheduleTarget, ScheduledSend},
+    traffic::{AddressMix, AddressType, JobSeed},
 };
 
 const MAX_DERIV: u32 = 2u32.pow(31) - 1;
@@ -52,6 +59,34 @@ fn bitcoind_default_cookie_path() -> String {
     path.to_str().expect("cookie path should be ok").to_string()
 }
 
+/// Parse `address` and check it belongs to `network`.
+pub fn parse_address(address: &str, network: Network) -> Result<Address, Error> {
+    let address = address.trim();
+    let unchecked = Address::from_str(address)
+        .map_err(|e| Error::InvalidAddress(format!("{}: {}", address, e)))?;
+    unchecked
+        .require_network(network)
+        .map_err(|_| Error::AddressNetwork {
+            address: address.to_string(),
+            expected: network,
+        })
+}
+
+/// Parse a list of addresses separated by whitespaces or commas, the error names the
+/// first invalid entry.
+pub fn parse_addresses(input: &str, network: Network) -> Result<Vec<Address>, Error> {
+    let addresses = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|addr| !addr.is_empty())
+        .map(|addr| parse_address(addr, network))
+        .collect::<Result<Vec<_>, _>>()?;
+    if addresses.is_empty() {
+        Err(Error::EmptyAddressList)
+    } else {
+        Ok(addresses)
+    }
+}
+
 fn config_path() -> String {
     #[cfg(target_os = "windows")]
     let mut path = {
@@ -74,18 +109,114 @@ fn config_path() -> String {
     path.to_str().expect("path should be ok").to_string()
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct Config {
     pub bitcoind: BitcoindConfig,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// Seed of the random generator, drawn on each connection if not set
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Named connection settings w/ default panel values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Profile {
+    pub name: String,
+    pub bitcoind: BitcoindConfig,
+    #[serde(default)]
+    pub defaults: PanelDefaults,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PanelDefaults {
+    pub generate_blocks: Option<String>,
+    pub generate_descriptor: Option<String>,
+    pub send_amount: Option<String>,
+    pub send_descriptor: Option<String>,
+    pub autoblock_blocks: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BitcoindConfig {
     pub auth_type: AuthMethod,
     pub user: String,
     pub password: String,
     pub cookie_path: String,
     pub address: String,
+    #[serde(default = "default_wallet")]
+    pub wallet: String,
+    #[serde(default = "saved_password_source")]
+    pub password_source: PasswordSource,
+    /// Environment variable name or file path, depending on `password_source`
+    #[serde(default)]
+    pub password_ref: String,
+}
+
+/// Where the RPC password comes from, only `Config` persists it in minta.conf.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum PasswordSource {
+    Config,
+    #[default]
+    NotSaved,
+    Env,
+    File,
+}
+
+impl Display for PasswordSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordSource::Config => write!(f, "saved in config"),
+            PasswordSource::NotSaved => write!(f, "not saved"),
+            PasswordSource::Env => write!(f, "env variable"),
+            PasswordSource::File => write!(f, "file"),
+        }
+    }
+}
+
+impl BitcoindConfig {
+    /// Resolve the RPC password from its source.
+    pub fn password(&self) -> Result<String, String> {
+        match self.password_source {
+            PasswordSource::Config | PasswordSource::NotSaved => Ok(self.password.clone()),
+            PasswordSource::Env => env::var(&self.password_ref)
+                .map_err(|e| format!("Fail to read ${}: {}", self.password_ref, e)),
+            PasswordSource::File => fs::read_to_string(&self.password_ref)
+                .map(|p| p.trim().to_string())
+                .map_err(|e| format!("Fail to read {}: {}", self.password_ref, e)),
+        }
+    }
+
+    /// Human readable origin of the RPC password.
+    pub fn password_origin(&self) -> String {
+        match self.password_source {
+            PasswordSource::Config => "password stored in plaintext in minta.conf".to_string(),
+            PasswordSource::NotSaved => "password typed, not saved".to_string(),
+            PasswordSource::Env => match env::var(&self.password_ref) {
+                Ok(_) => format!("password from ${}", self.password_ref),
+                Err(_) => format!("password from ${} (not set!)", self.password_ref),
+            },
+            PasswordSource::File => format!("password from file {}", self.password_ref),
+        }
+    }
+
+    /// Drop the password if it should not be persisted.
+    fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        if config.password_source != PasswordSource::Config {
+            config.password = String::new();
+        }
+        config
+    }
+}
+
+/// Configs written before the password source existed saved the password.
+fn saved_password_source() -> PasswordSource {
+    PasswordSource::Config
+}
+
+fn default_wallet() -> String {
+    bitcoind::DEFAULT_WALLET_NAME.to_string()
 }
 
 impl Default for BitcoindConfig {
@@ -96,6 +227,9 @@ impl Default for BitcoindConfig {
             password: "password".into(),
             cookie_path: bitcoind_default_cookie_path(),
             address: "127.0.0.1:18443".into(),
+            wallet: default_wallet(),
+            password_source: PasswordSource::default(),
+            password_ref: String::new(),
         }
     }
 }
@@ -110,6 +244,18 @@ impl Config {
         Self::default()
     }
 
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Insert `profile`, replacing the profile w/ the same name if any.
+    pub fn upsert_profile(&mut self, profile: Profile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(p) => *p = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
     pub fn save(&self) -> Result<(), String> {
         log::info!("save({})", config_path());
         let path = config_path();
@@ -118,14 +264,29 @@ impl Config {
         if !parent.exists() {
             let _ = fs::create_dir_all(parent);
         }
-        if let Ok(file) = fs::File::create(path) {
-            match serde_yaml::to_writer(file, self) {
-                Ok(_) => Ok(()),
-                Err(e) => Err(format!("Failed to write config file: {}", e)),
+        let mut config = self.clone();
+        config.bitcoind = config.bitcoind.redacted();
+        for profile in &mut config.profiles {
+            profile.bitcoind = profile.bitcoind.redacted();
+        }
+        // the config may contain credentials, only the user can read it: a new file is
+        // created w/ 0600, an existing one is restricted before being truncated
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            if p.exists() {
+                fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+                    .map_err(|e| format!("Failed to set config file permissions: {}", e))?;
             }
-        } else {
-            Err("Failed to open config file".to_string())
         }
+        let file = options
+            .open(&path)
+            .map_err(|e| format!("Failed to open config file: {}", e))?;
+        serde_yaml::to_writer(file, &config)
+            .map_err(|e| format!("Failed to write config file: {}", e))
     }
 }
 
@@ -143,6 +304,17 @@ pub enum Message {
     User(String),
     Password(String),
     CookiePath(String),
+    WalletName(String),
+    PasswordSource(PasswordSource),
+    PasswordRef(String),
+    Profile(String),
+    ProfileName(String),
+    NewWalletName(String),
+    NewWalletPassphrase(String),
+    UnlockPassphrase(String),
+    SignetKey(String),
+    SeedInput(String),
+    SelectWallet(String),
     GenerateTarget(GenerateTarget),
     BlocksGenerate(String),
     AddressGenerate(String),
@@ -158,8 +330,54 @@ pub enum Message {
     BlockSend(String),
     AutoblockBlocks(String),
     AutoblockTimeframe(TimeFrame),
+    AutoblockModel(IntervalModel),
+    AutoblockReplayPath(String),
+    AutoblockTarget(GenerateTarget),
+    AutoblockTargetInput(String),
+    AutoblockDescriptorIndex(String),
     ConsoleEdit(Action),
     InvalidateBlocks(String),
+    Tab(Tab),
+    ExternalDescriptor(String),
+    ExternalAmount(String),
+    ExternalAddress(String),
+    ExternalBackMin(String),
+    ExternalBackMax(String),
+    ExternalBackBlocks(String),
+    Psbt(String),
+    PsbtOthers(String),
+    RawTx(String),
+    BlockRawTxs(String),
+    BlockTarget(GenerateTarget),
+    BlockAddress(String),
+    BootstrapAmount(String),
+    CensorKind(CensorKind),
+    CensorInput(String),
+    CensorBlocks(String),
+    ScheduleTarget(GenerateTarget),
+    ScheduleInput(String),
+    ScheduleIndex(String),
+    ScheduleMin(String),
+    ScheduleMax(String),
+    ScheduleEvery(String),
+    ScheduleUnit(FrequencyUnit),
+    ActorName(String),
+    ActorKind(ActorKind),
+    ActorBlocks(String),
+    ActorOutputs(String),
+    ActorRate(String),
+    ActorUtxos(String),
+    ActorMin(String),
+    ActorMax(String),
+    ActorFunding(String),
+    TimelockHeight(String),
+    TimelockOutpoint(String),
+    TimelockConfirmations(String),
+    TimelockTimestamp(String),
+    MockTime(String),
+    MockTimeJump(String),
+    MockTimeJumpUnit(JumpUnit),
+    MockTimeStep(String),
 
     // buttons
     SelectRpcAuth(bool),
@@ -171,6 +389,7 @@ pub enum Message {
     StopAutoblock,
     GenerateToAddress,
     GenerateToSelf,
+    Bootstrap,
     GenerateToRandom,
     GenerateToDescriptor,
     GetNewAddress,
@@ -178,6 +397,51 @@ pub enum Message {
     SendToAddress,
     SendToDescriptor,
     ToggleEveryBlock(bool),
+    LoadExternal,
+    ExternalSendToAddress,
+    ExternalSendToSelf,
+    ExternalSweep,
+    ToggleSendBack(bool),
+    ToggleSendBackSweep(bool),
+    DecodePsbt,
+    SignPsbt,
+    CombinePsbt,
+    FinalizePsbt,
+    BroadcastPsbt,
+    DecodeRawTx,
+    TestRawTx,
+    BroadcastRawTx,
+    ToggleRawTxMine(bool),
+    RefreshMempool,
+    ToggleBlockTx(Txid, bool),
+    SelectAllMempool(bool),
+    BuildBlock,
+    AddCensorRule,
+    RemoveCensorRule(usize),
+    AddSchedule,
+    RemoveSchedule(ScheduleId),
+    AddActor,
+    RemoveActor(ActorId),
+    MineUntilHeight,
+    MineUntilConfirmed,
+    AdvanceMtp,
+    SetMockTime,
+    ResetMockTime,
+    JumpMockTime,
+    ToggleMockTimeStep(bool),
+    CancelJob(JobId),
+    ClearJobs,
+    ToggleNewWalletDescriptors(bool),
+    ToggleNewWalletPrivateKeys(bool),
+    CreateWallet,
+    LoadWallet(String),
+    UnloadWallet(String),
+    UnlockWallet,
+    SetSignetKey,
+    SetSeed,
+    ToggleAddressType(AddressType, bool),
+    RefreshWallets,
+    SaveProfile,
 
     KeyPressed(Key),
 
@@ -187,10 +451,10 @@ pub enum Message {
 #[derive(Debug)]
 pub struct Flags {
     pub receiver: async_channel::Receiver<BitcoinMessage>,
-    pub sender: std::sync::mpsc::Sender<BitcoinMessage>,
+    pub sender: async_channel::Sender<BitcoinMessage>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum AuthMethod {
     #[default]
     RpcAuth,
@@ -203,6 +467,7 @@ pub enum GenerateTarget {
     ToSelf,
     Random,
     Descriptor,
+    RoundRobin,
 }
 
 impl FromStr for GenerateTarget {
@@ -214,6 +479,7 @@ impl FromStr for GenerateTarget {
             "self" => Ok(GenerateTarget::ToSelf),
             "random" => Ok(GenerateTarget::Random),
             "descriptor" => Ok(GenerateTarget::Descriptor),
+            "address list" => Ok(GenerateTarget::RoundRobin),
             _ => Err(()),
         }
     }
@@ -226,6 +492,137 @@ impl Display for GenerateTarget {
             GenerateTarget::ToSelf => write!(f, "self"),
             GenerateTarget::Random => write!(f, "random"),
             GenerateTarget::Descriptor => write!(f, "descriptor"),
+            GenerateTarget::RoundRobin => write!(f, "address list"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tab {
+    Main,
+    External,
+    Psbt,
+    RawTx,
+    BlockBuilder,
+    Censorship,
+    Scheduler,
+    Actors,
+    Timelock,
+    Time,
+    Jobs,
+    Wallets,
+}
+
+impl Display for Tab {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Tab::Main => write!(f, "main"),
+            Tab::External => write!(f, "external wallet"),
+            Tab::Psbt => write!(f, "psbt"),
+            Tab::RawTx => write!(f, "raw transaction"),
+            Tab::BlockBuilder => write!(f, "block builder"),
+            Tab::Censorship => write!(f, "censorship"),
+            Tab::Scheduler => write!(f, "scheduled sends"),
+            Tab::Actors => write!(f, "actors"),
+            Tab::Timelock => write!(f, "timelock"),
+            Tab::Time => write!(f, "mock time"),
+            Tab::Jobs => write!(f, "jobs"),
+            Tab::Wallets => write!(f, "wallets"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JumpUnit {
+    Hour,
+    Day,
+    Week,
+}
+
+impl JumpUnit {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            JumpUnit::Hour => 3_600,
+            JumpUnit::Day => 86_400,
+            JumpUnit::Week => 604_800,
+        }
+    }
+}
+
+impl Display for JumpUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JumpUnit::Hour => write!(f, "hours"),
+            JumpUnit::Day => write!(f, "days"),
+            JumpUnit::Week => write!(f, "weeks"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrequencyUnit {
+    Blocks,
+    Seconds,
+    Minutes,
+}
+
+impl Display for FrequencyUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FrequencyUnit::Blocks => write!(f, "blocks"),
+            FrequencyUnit::Seconds => write!(f, "seconds"),
+            FrequencyUnit::Minutes => write!(f, "minutes"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActorKind {
+    Exchange,
+    Retail,
+    Consolidator,
+}
+
+impl Display for ActorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ActorKind::Exchange => write!(f, "exchange"),
+            ActorKind::Retail => write!(f, "retail"),
+            ActorKind::Consolidator => write!(f, "consolidator"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CensorKind {
+    Descriptor,
+    FeeRate,
+    Txids,
+}
+
+impl Display for CensorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CensorKind::Descriptor => write!(f, "paying to descriptor"),
+            CensorKind::FeeRate => write!(f, "fee rate below"),
+            CensorKind::Txids => write!(f, "txids"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntervalModel {
+    Fixed,
+    Exponential,
+    Replay,
+}
+
+impl Display for IntervalModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IntervalModel::Fixed => write!(f, "fixed"),
+            IntervalModel::Exponential => write!(f, "exponential"),
+            IntervalModel::Replay => write!(f, "replay"),
         }
     }
 }
@@ -259,10 +656,15 @@ impl FromStr for TimeFrame {
 
 pub struct Gui {
     receiver: async_channel::Receiver<BitcoinMessage>,
-    sender: std::sync::mpsc::Sender<BitcoinMessage>,
+    sender: async_channel::Sender<BitcoinMessage>,
     config: Config,
+    profile: Option<String>,
+    profile_name: String,
     block_height: Option<u64>,
-    balance: Option<Amount>,
+    median_time: Option<u64>,
+    mock_time: Option<u64>,
+    balance: Option<Balances>,
+    bootstrap_amount: String,
     generate_target: GenerateTarget,
     generate_blocks: String,
     generate_address: String,
@@ -282,9 +684,86 @@ pub struct Gui {
     connected: bool,
     autoblock_blocks: String,
     autoblocks_timeframe: TimeFrame,
+    autoblock_model: IntervalModel,
+    autoblock_replay_path: String,
+    next_block: Option<(chrono::DateTime<chrono::Local>, time::Duration)>,
+    autoblock_target: GenerateTarget,
+    autoblock_target_input: String,
+    autoblock_descriptor_index: String,
     autoblock_wip: bool,
-    generate_wip: bool,
-    send_wip: bool,
+    jobs: BTreeMap<JobId, JobInfo>,
+    health: NodeHealth,
+    network: Option<Network>,
+    signet_key: String,
+    signet_key_set: bool,
+    seed: Option<u64>,
+    seed_input: String,
+    address_mix: AddressMix,
+    wallet_active: Option<String>,
+    wallets_loaded: Vec<String>,
+    wallets_available: Vec<String>,
+    new_wallet_name: String,
+    new_wallet_descriptors: bool,
+    new_wallet_private_keys: bool,
+    new_wallet_passphrase: String,
+    unlock_passphrase: String,
+    tab: Tab,
+    external_descriptor: String,
+    external_loaded: bool,
+    external_balance: Option<Amount>,
+    external_amount: String,
+    external_address: String,
+    external_back_min: String,
+    external_back_max: String,
+    external_back_blocks: String,
+    external_back_enabled: bool,
+    external_back_sweep: bool,
+    external_wip: bool,
+    psbt: String,
+    psbt_others: String,
+    psbt_summary: Option<String>,
+    psbt_wip: bool,
+    raw_tx: String,
+    raw_tx_mine: bool,
+    raw_tx_summary: Option<String>,
+    raw_tx_wip: bool,
+    mempool: Vec<MempoolTx>,
+    block_selected: BTreeSet<Txid>,
+    block_raw_txs: String,
+    block_target: GenerateTarget,
+    block_address: String,
+    block_wip: bool,
+    censor_rules: Vec<CensorRule>,
+    censor_kind: CensorKind,
+    censor_input: String,
+    censor_blocks: String,
+    schedules: Vec<ScheduledSend>,
+    schedule_target: GenerateTarget,
+    schedule_input: String,
+    schedule_index: String,
+    schedule_min: String,
+    schedule_max: String,
+    schedule_every: String,
+    schedule_unit: FrequencyUnit,
+    actors: Vec<Actor>,
+    actor_name: String,
+    actor_kind: ActorKind,
+    actor_blocks: String,
+    actor_outputs: String,
+    actor_rate: String,
+    actor_utxos: String,
+    actor_min: String,
+    actor_max: String,
+    actor_funding: String,
+    timelock_height: String,
+    timelock_outpoint: String,
+    timelock_confirmations: String,
+    timelock_timestamp: String,
+    mock_time_input: String,
+    mock_time_jump: String,
+    mock_time_jump_unit: JumpUnit,
+    mock_time_step: String,
+    mock_time_step_enabled: bool,
     console: Content,
 }
 
@@ -311,7 +790,7 @@ impl Gui {
     }
 
     pub fn send_to_bitcoind(&mut self, msg: BitcoinMessage) {
-        if let Err(e) = self.sender.send(msg) {
+        if let Err(e) = self.sender.try_send(msg) {
             self.print(&format!("Fail to send message to bitcoind: {}", e))
         }
     }
@@ -321,15 +800,24 @@ impl Gui {
     }
 
     pub fn connect_rpc_auth(&mut self) {
+        let password = match self.config.bitcoind.password() {
+            Ok(password) => password,
+            Err(e) => {
+                self.print(&e);
+                return;
+            }
+        };
         let msg = BitcoinMessage::SetCredentials {
             address: self.config.bitcoind.address.clone(),
             auth: bitcoind::AuthMethod::RpcAuth {
                 user: self.config.bitcoind.user.clone(),
-                password: self.config.bitcoind.password.clone(),
+                password,
             },
+            wallet: self.config.bitcoind.wallet.clone(),
         };
 
         self.send_to_bitcoind(msg);
+        self.send_to_bitcoind(BitcoinMessage::SetSeed(self.config_seed()));
         self.send_to_bitcoind(BitcoinMessage::Connect);
     }
 
@@ -339,22 +827,123 @@ impl Gui {
             auth: bitcoind::AuthMethod::Cookie {
                 cookie_path: self.config.bitcoind.cookie_path.clone(),
             },
+            wallet: self.config.bitcoind.wallet.clone(),
         };
 
         self.send_to_bitcoind(msg);
+        self.send_to_bitcoind(BitcoinMessage::SetSeed(self.config_seed()));
         self.send_to_bitcoind(BitcoinMessage::Connect);
     }
 
+    /// Main seed of the config, runs start from the first job.
+    fn config_seed(&self) -> Option<JobSeed> {
+        self.config.seed.map(|seed| JobSeed { seed, job: 0 })
+    }
+
+    /// Reseed the service random generator w/ the input seed (a random one if empty)
+    /// and keep it in the config so the next runs use the same seed, a `/<nth job>`
+    /// suffix replays that job next.
+    pub fn set_seed(&mut self) {
+        let seed = JobSeed::from_str(self.seed_input.trim_end_matches('/')).ok();
+        self.config.seed = seed.map(|seed| seed.seed);
+        if let Err(e) = self.config.save() {
+            self.print(&e);
+        }
+        self.send_to_bitcoind(BitcoinMessage::SetSeed(seed));
+    }
+
+    pub fn load_profile(&mut self, name: String) {
+        if let Some(profile) = self.config.profile(&name).cloned() {
+            self.config.bitcoind = profile.bitcoind;
+            let defaults = profile.defaults;
+            let apply = |field: &mut String, value: Option<String>| {
+                if let Some(value) = value {
+                    *field = value;
+                }
+            };
+            apply(&mut self.generate_blocks, defaults.generate_blocks);
+            apply(&mut self.generate_descriptor, defaults.generate_descriptor);
+            apply(&mut self.send_amount, defaults.send_amount);
+            apply(&mut self.send_descriptor, defaults.send_descriptor);
+            apply(&mut self.autoblock_blocks, defaults.autoblock_blocks);
+            self.profile_name = name.clone();
+            self.profile = Some(name);
+        }
+    }
+
+    /// Save the connection settings & actual panel values as a profile.
+    pub fn save_profile(&mut self) {
+        let name = self.profile_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let non_empty = |value: &str| {
+            if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        };
+        let defaults = PanelDefaults {
+            generate_blocks: non_empty(&self.generate_blocks),
+            generate_descriptor: non_empty(&self.generate_descriptor),
+            send_amount: non_empty(&self.send_amount),
+            send_descriptor: non_empty(&self.send_descriptor),
+            autoblock_blocks: non_empty(&self.autoblock_blocks),
+        };
+        self.config.upsert_profile(Profile {
+            name: name.clone(),
+            bitcoind: self.config.bitcoind.clone(),
+            defaults,
+        });
+        match self.config.save() {
+            Ok(_) => self.print(&format!("Profile {} saved", name)),
+            Err(e) => self.print(&e),
+        }
+        self.profile = Some(name);
+    }
+
+    pub fn create_wallet(&mut self) {
+        let name = self.new_wallet_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let passphrase = if self.new_wallet_passphrase.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.new_wallet_passphrase))
+        };
+        self.send_to_bitcoind(BitcoinMessage::CreateWallet(bitcoind::CreateWallet {
+            name,
+            descriptors: self.new_wallet_descriptors,
+            private_keys: self.new_wallet_private_keys,
+            passphrase,
+        }));
+        self.new_wallet_name.clear();
+    }
+
     pub fn credentials_valid(&self) -> bool {
         match self.config.bitcoind.auth_type {
             AuthMethod::RpcAuth => {
+                let bitcoind = &self.config.bitcoind;
+                let password = match bitcoind.password_source {
+                    // typed in the auth panel, `NotSaved` is empty again after a restart
+                    PasswordSource::Config | PasswordSource::NotSaved => {
+                        !bitcoind.password.is_empty()
+                    }
+                    PasswordSource::Env => bitcoind.password().is_ok_and(|p| !p.is_empty()),
+                    // read on connect, not on every frame
+                    PasswordSource::File => !bitcoind.password_ref.is_empty(),
+                };
                 !self.config.bitcoind.user.is_empty()
-                    && !self.config.bitcoind.password.is_empty()
+                    && password
                     && !self.config.bitcoind.address.is_empty()
+                    && !self.config.bitcoind.wallet.is_empty()
             }
             AuthMethod::Cookie => {
                 !self.config.bitcoind.cookie_path.is_empty()
                     && !self.config.bitcoind.address.is_empty()
+                    && !self.config.bitcoind.wallet.is_empty()
             }
         }
     }
@@ -365,6 +954,13 @@ impl Gui {
         }
     }
 
+    pub fn bootstrap(&mut self) {
+        match Amount::from_str_in(&self.bootstrap_amount, Denomination::Bitcoin) {
+            Ok(amount) => self.send_to_bitcoind(BitcoinMessage::Bootstrap(amount)),
+            Err(e) => self.print(&format!("Invalid bootstrap amount: {}", e)),
+        }
+    }
+
     pub fn generate_to_random(&mut self) {
         if let Ok(blocks) = u32::from_str(&self.generate_blocks) {
             self.send_to_bitcoind(BitcoinMessage::Generate(blocks));
@@ -372,18 +968,15 @@ impl Gui {
     }
 
     pub fn generate_to_address(&mut self) {
-        if let (Ok(blocks), Ok(addr)) = (
-            u32::from_str(&self.generate_blocks),
-            Address::from_str(&self.generate_address),
-        ) {
-            if addr.is_valid_for_network(miniscript::bitcoin::Network::Regtest) {
-                let address = addr.assume_checked();
-                self.send_to_bitcoind(BitcoinMessage::GenerateToAddress(GenerateToAddress {
-                    blocks,
-                    address,
-                }))
-            } else {
-                self.print("Invalid address network!")
+        if let Ok(blocks) = u32::from_str(&self.generate_blocks) {
+            match parse_address(&self.generate_address, self.network()) {
+                Ok(address) => {
+                    self.send_to_bitcoind(BitcoinMessage::GenerateToAddress(GenerateToAddress {
+                        blocks,
+                        address,
+                    }))
+                }
+                Err(e) => self.print(&e.report("Cannot generate")),
             }
         }
     }
@@ -403,18 +996,15 @@ impl Gui {
     }
 
     pub fn send_to_address(&mut self) {
-        if let (Ok(amount), Ok(addr)) = (
-            Amount::from_str_in(&self.send_amount, Denomination::Bitcoin),
-            Address::from_str(&self.send_address),
-        ) {
-            if addr.is_valid_for_network(miniscript::bitcoin::Network::Regtest) {
-                let address = addr.assume_checked();
-                self.send_to_bitcoind(BitcoinMessage::SendToAddress(SendToAddress {
-                    amount,
-                    address,
-                }))
-            } else {
-                self.print("Invalid address network!")
+        if let Ok(amount) = Amount::from_str_in(&self.send_amount, Denomination::Bitcoin) {
+            match parse_address(&self.send_address, self.network()) {
+                Ok(address) => {
+                    self.send_to_bitcoind(BitcoinMessage::SendToAddress(SendToAddress {
+                        amount,
+                        address,
+                    }))
+                }
+                Err(e) => self.print(&e.report("Cannot send")),
             }
         }
     }
@@ -441,12 +1031,11 @@ impl Gui {
         let count = u32::from_str(&self.send_count);
         let min = Amount::from_str_in(&self.send_min, Denomination::Bitcoin);
         let max = Amount::from_str_in(&self.send_max, Denomination::Bitcoin);
-        let descriptor =
-            if Descriptor::<DescriptorPublicKey>::from_str(&self.send_descriptor).is_ok() {
-                Some(self.send_descriptor.clone())
-            } else {
-                None
-            };
+        let descriptor = if parse_descriptor(&self.send_descriptor).is_ok() {
+            Some(self.send_descriptor.clone())
+        } else {
+            None
+        };
         let start_index = u32::from_str(&self.send_descriptor_index);
         let every_blocks = u32::from_str(&self.send_every_blocks);
         if let (
@@ -470,83 +1059,562 @@ impl Gui {
         }
     }
 
-    pub fn start_auto_block(&mut self) {
-        log::info!("GUI.start_auto_block()");
-        if !self.generate_wip && !self.send_wip && !self.autoblock_wip {
-            self.autoblock_wip = true;
-            let tf_ms = match self.autoblocks_timeframe {
-                TimeFrame::Second => 1_000,
-                TimeFrame::Minute => 60_000,
-            };
-            let blocks = u32::from_str(&self.autoblock_blocks).expect("input checked");
-            let delay = time::Duration::from_millis((tf_ms / blocks) as u64);
-            log::info!("start");
-            self.send_to_bitcoind(BitcoinMessage::StartAutoBlock(delay));
+    pub fn load_external(&mut self) {
+        if !self.external_descriptor.is_empty() {
+            self.external_wip = true;
+            self.send_to_bitcoind(BitcoinMessage::SetExternalWallet(
+                self.external_descriptor.clone(),
+            ));
         }
+    }
 
-        if self.send_every_blocks_enabled {
-            self.toggle_every_blocks(true);
+    pub fn external_send_to_address(&mut self) {
+        if let Ok(amount) = Amount::from_str_in(&self.external_amount, Denomination::Bitcoin) {
+            match parse_address(&self.external_address, self.network()) {
+                Ok(address) => {
+                    self.external_wip = true;
+                    self.send_to_bitcoind(BitcoinMessage::ExternalSendToAddress(SendToAddress {
+                        amount,
+                        address,
+                    }))
+                }
+                Err(e) => self.print(&e.report("Cannot send")),
+            }
         }
     }
 
-    pub fn stop_auto_block(&mut self) {
-        if self.autoblock_wip {
-            self.send_to_bitcoind(BitcoinMessage::StopAutoBlock);
+    pub fn external_send_to_self(&mut self) {
+        if let Ok(amount) = Amount::from_str_in(&self.external_amount, Denomination::Bitcoin) {
+            self.external_wip = true;
+            self.send_to_bitcoind(BitcoinMessage::ExternalSendToSelf(amount))
         }
     }
 
-    pub fn print(&mut self, msg: &str) {
-        let mut msg = msg.to_string();
-        if !msg.ends_with('\n') {
-            msg.push('\n');
+    pub fn toggle_send_back(&mut self, state: bool) {
+        if !state {
+            self.external_back_enabled = false;
+            self.send_to_bitcoind(BitcoinMessage::DisableSendBack);
+            return;
+        }
+        let (min, max) = if self.external_back_sweep {
+            // amounts are not used when sweeping
+            (Ok(Amount::ZERO), Ok(Amount::ZERO))
+        } else {
+            (
+                Amount::from_str_in(&self.external_back_min, Denomination::Bitcoin),
+                Amount::from_str_in(&self.external_back_max, Denomination::Bitcoin),
+            )
+        };
+        let blocks = u32::from_str(&self.external_back_blocks);
+        if let (Ok(amount_min), Ok(amount_max), Ok(blocks)) = (min, max, blocks) {
+            if blocks > 0 && (self.external_back_sweep || amount_min < amount_max) {
+                self.external_back_enabled = true;
+                self.send_to_bitcoind(BitcoinMessage::EnableSendBack(SendBack {
+                    amount_min,
+                    amount_max,
+                    blocks,
+                    sweep: self.external_back_sweep,
+                    blocks_left: None,
+                }))
+            }
         }
+    }
 
-        self.console
-            .perform(Action::Edit(Edit::Paste(Arc::new(msg))));
+    pub fn psbt_action(&mut self, msg: BitcoinMessage) {
+        if !self.psbt.is_empty() {
+            self.psbt_wip = true;
+            self.send_to_bitcoind(msg);
+        }
     }
 
-    pub fn auth_panel(&self) -> Container<Message> {
-        let address_input = {
-            let mut input =
-                TextInput::new("bitcoind address", &self.config.bitcoind.address).width(310);
-            if !self.connected {
-                input = input.on_input(Message::BitcoindAddress);
-            }
-            input
+    pub fn combine_psbt(&mut self) {
+        let mut psbts = vec![self.psbt.clone()];
+        psbts.extend(self.psbt_others.split_whitespace().map(|p| p.to_string()));
+        if psbts.len() > 1 {
+            self.psbt_action(BitcoinMessage::CombinePsbt(psbts));
+        } else {
+            self.print("Need at least 2 PSBTs to combine!")
+        }
+    }
+
+    pub fn raw_tx_action(&mut self, msg: BitcoinMessage) {
+        if !self.raw_tx.is_empty() {
+            self.raw_tx_wip = true;
+            self.send_to_bitcoind(msg);
+        }
+    }
+
+    pub fn build_block(&mut self) {
+        let payout = match self.block_target {
+            GenerateTarget::ToSelf => PayoutTarget::ToSelf,
+            GenerateTarget::Address => match parse_address(&self.block_address, self.network()) {
+                Ok(address) => PayoutTarget::Address(address),
+                Err(e) => {
+                    self.print(&e.report("Invalid coinbase address"));
+                    return;
+                }
+            },
+            _ => PayoutTarget::Random,
         };
+        // keep the mempool order, so parents come before their children
+        let txids = self
+            .mempool
+            .iter()
+            .map(|tx| tx.txid)
+            .filter(|txid| self.block_selected.contains(txid))
+            .collect();
+        let raw_txs = self
+            .block_raw_txs
+            .split_whitespace()
+            .map(|hex| hex.to_string())
+            .collect();
+        self.block_wip = true;
+        self.send_to_bitcoind(BitcoinMessage::BuildBlock(BuildBlock {
+            txids,
+            raw_txs,
+            payout,
+        }));
+    }
 
-        let (cookie, rpc_auth) = match (&self.config.bitcoind.auth_type, self.connected) {
-            (_, true) => (false, false),
-            (AuthMethod::RpcAuth, false) => (false, true),
-            (AuthMethod::Cookie, false) => (true, false),
+    pub fn add_schedule(&mut self) {
+        let input = self.schedule_input.trim();
+        let network = self.network();
+        let target = match self.schedule_target {
+            GenerateTarget::RoundRobin => {
+                parse_addresses(input, network).map(ScheduleTarget::Addresses)
+            }
+            GenerateTarget::Descriptor => {
+                parse_descriptor(input).map(|descriptor| ScheduleTarget::Descriptor {
+                    descriptor,
+                    start_index: u32::from_str(&self.schedule_index).unwrap_or(0),
+                })
+            }
+            _ => Ok(ScheduleTarget::Random),
+        };
+        let target = match target {
+            Ok(target) => target,
+            Err(e) => {
+                self.print(&e.report("Invalid scheduled send target"));
+                return;
+            }
+        };
+        let (amount_min, amount_max) = match (
+            Amount::from_str_in(&self.schedule_min, Denomination::Bitcoin),
+            Amount::from_str_in(&self.schedule_max, Denomination::Bitcoin),
+        ) {
+            (Ok(min), Ok(max)) if min <= max => (min, max),
+            _ => {
+                self.print("Invalid scheduled send amount range!");
+                return;
+            }
+        };
+        let every = match u32::from_str(&self.schedule_every) {
+            Ok(every) if every > 0 => every,
+            _ => {
+                self.print("Invalid scheduled send frequency!");
+                return;
+            }
+        };
+        let frequency = match self.schedule_unit {
+            FrequencyUnit::Blocks => Frequency::Blocks(every),
+            FrequencyUnit::Seconds => Frequency::Interval(time::Duration::from_secs(every as u64)),
+            FrequencyUnit::Minutes => {
+                Frequency::Interval(time::Duration::from_secs(every as u64 * 60))
+            }
         };
+        self.send_to_bitcoind(BitcoinMessage::AddSchedule(Schedule {
+            target,
+            amount_min,
+            amount_max,
+            frequency,
+        }));
+    }
 
-        let connect = if self.connected {
-            Self::button("Disconnect", Some(Message::Disconnect))
+    pub fn add_actor(&mut self) {
+        let profile = match self.actor_kind {
+            ActorKind::Exchange => {
+                match (
+                    u32::from_str(&self.actor_blocks),
+                    u32::from_str(&self.actor_outputs),
+                ) {
+                    (Ok(blocks), Ok(outputs)) if blocks > 0 && outputs > 0 => {
+                        actor::Profile::Exchange { blocks, outputs }
+                    }
+                    _ => {
+                        self.print("Invalid exchange batch!");
+                        return;
+                    }
+                }
+            }
+            ActorKind::Retail => match f64::from_str(&self.actor_rate) {
+                Ok(per_hour) if per_hour > 0.0 && per_hour.is_finite() => {
+                    actor::Profile::Retail { per_hour }
+                }
+                _ => {
+                    self.print("Invalid retail tx rate!");
+                    return;
+                }
+            },
+            ActorKind::Consolidator => match u32::from_str(&self.actor_utxos) {
+                Ok(min_utxos) if min_utxos > 1 => actor::Profile::Consolidator { min_utxos },
+                _ => {
+                    self.print("Invalid consolidator coin count!");
+                    return;
+                }
+            },
+        };
+        let (amount_min, amount_max) = if self.actor_kind == ActorKind::Consolidator {
+            (Amount::ZERO, Amount::ZERO)
         } else {
-            Self::button(
-                "Connect",
-                match (&self.config.bitcoind.auth_type, self.credentials_valid()) {
-                    (_, false) => None,
-                    (AuthMethod::RpcAuth, true) => Some(Message::ConnectRpcAuth),
-                    (AuthMethod::Cookie, true) => Some(Message::ConnectCookie),
-                },
-            )
+            match (
+                Amount::from_str_in(&self.actor_min, Denomination::Bitcoin),
+                Amount::from_str_in(&self.actor_max, Denomination::Bitcoin),
+            ) {
+                (Ok(min), Ok(max)) if min <= max => (min, max),
+                _ => {
+                    self.print("Invalid actor amount range!");
+                    return;
+                }
+            }
         };
+        let funding = if self.actor_funding.is_empty() {
+            Amount::ZERO
+        } else {
+            match Amount::from_str_in(&self.actor_funding, Denomination::Bitcoin) {
+                Ok(funding) => funding,
+                Err(_) => {
+                    self.print("Invalid actor funding!");
+                    return;
+                }
+            }
+        };
+        self.send_to_bitcoind(BitcoinMessage::AddActor(NewActor {
+            name: self.actor_name.trim().to_string(),
+            profile,
+            amount_min,
+            amount_max,
+            funding,
+        }));
+    }
 
-        let mut rpc_auth_check = Checkbox::new("", rpc_auth);
-        let mut cookie_check = Checkbox::new("", cookie);
-        if !self.connected {
-            rpc_auth_check = rpc_auth_check.on_toggle(Message::SelectRpcAuth);
-            cookie_check = cookie_check.on_toggle(Message::SelectCookie);
-        }
-
-        let chain_height = self
-            .block_height
-            .map(|height| Text::new(format!("Block height {}", height)));
-
-        let col = Column::new()
-            .push(
+    pub fn add_censor_rule(&mut self) {
+        let blocks = match u32::from_str(&self.censor_blocks) {
+            Ok(blocks) if blocks > 0 => blocks,
+            _ => {
+                self.print("Invalid censorship block count!");
+                return;
+            }
+        };
+        let input = self.censor_input.trim();
+        let criteria = match self.censor_kind {
+            // the scripts are derived by the service
+            CensorKind::Descriptor => match CensorCriteria::descriptor(input) {
+                Ok(criteria) => criteria,
+                Err(e) => {
+                    self.print(&e.report("Invalid censorship descriptor"));
+                    return;
+                }
+            },
+            CensorKind::FeeRate => match f64::from_str(input) {
+                Ok(rate) if rate >= 0.0 => CensorCriteria::FeeRateBelow(rate),
+                _ => {
+                    self.print("Invalid censorship fee rate!");
+                    return;
+                }
+            },
+            CensorKind::Txids => {
+                match input
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|txid| !txid.is_empty())
+                    .map(Txid::from_str)
+                    .collect::<Result<BTreeSet<_>, _>>()
+                {
+                    Ok(txids) if !txids.is_empty() => CensorCriteria::Txids(txids),
+                    _ => {
+                        self.print("Invalid censorship txids!");
+                        return;
+                    }
+                }
+            }
+        };
+        self.censor_rules.push(CensorRule { criteria, blocks });
+        self.censor_input.clear();
+        self.update_censor_rules();
+    }
+
+    /// Forward the rules to the running autoblock miner.
+    fn update_censor_rules(&mut self) {
+        if self.autoblock_wip {
+            self.send_to_bitcoind(BitcoinMessage::SetCensorRules(self.censor_rules.clone()));
+        }
+    }
+
+    /// Parse a timestamp either as unix seconds or as `%Y-%m-%d %H:%M:%S` (UTC).
+    pub fn parse_timestamp(input: &str) -> Option<u64> {
+        if let Ok(timestamp) = u64::from_str(input) {
+            return Some(timestamp);
+        }
+        chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .and_then(|dt| u64::try_from(dt.and_utc().timestamp()).ok())
+    }
+
+    pub fn mine_until_height(&mut self) {
+        if let Ok(height) = u64::from_str(&self.timelock_height) {
+            self.send_to_bitcoind(BitcoinMessage::MineUntilHeight(height));
+        }
+    }
+
+    pub fn mine_until_confirmed(&mut self) {
+        match (
+            OutPoint::from_str(self.timelock_outpoint.trim()),
+            u32::from_str(&self.timelock_confirmations),
+        ) {
+            (Ok(outpoint), Ok(confirmations)) => {
+                self.send_to_bitcoind(BitcoinMessage::MineUntilConfirmed {
+                    outpoint,
+                    confirmations,
+                });
+            }
+            (Err(_), _) => self.print("Invalid outpoint, expected <txid>:<vout>!"),
+            _ => {}
+        }
+    }
+
+    pub fn advance_mtp(&mut self) {
+        if let Some(timestamp) = Self::parse_timestamp(self.timelock_timestamp.trim()) {
+            self.send_to_bitcoind(BitcoinMessage::AdvanceMtp(timestamp));
+        } else {
+            self.print("Invalid timestamp!")
+        }
+    }
+
+    pub fn set_mock_time(&mut self) {
+        if let Some(timestamp) = Self::parse_timestamp(self.mock_time_input.trim()) {
+            self.send_to_bitcoind(BitcoinMessage::SetMockTime(timestamp));
+        } else {
+            self.print("Invalid timestamp!")
+        }
+    }
+
+    pub fn jump_mock_time(&mut self) {
+        if let Ok(count) = u64::from_str(&self.mock_time_jump) {
+            let seconds = count * self.mock_time_jump_unit.seconds();
+            self.send_to_bitcoind(BitcoinMessage::AdvanceMockTime(seconds));
+        }
+    }
+
+    pub fn toggle_mock_time_step(&mut self, state: bool) {
+        if !state {
+            self.mock_time_step_enabled = false;
+            self.send_to_bitcoind(BitcoinMessage::SetMockTimeStep(None));
+        } else if let Ok(step) = u64::from_str(&self.mock_time_step) {
+            self.mock_time_step_enabled = true;
+            self.send_to_bitcoind(BitcoinMessage::SetMockTimeStep(Some(step)));
+        }
+    }
+
+    pub fn format_timestamp(timestamp: u64) -> String {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+
+    /// Build the autoblock payout target from the GUI inputs.
+    pub fn autoblock_payout(&mut self) -> Option<PayoutTarget> {
+        let input = self.autoblock_target_input.trim();
+        let network = self.network();
+        let payout = match self.autoblock_target {
+            GenerateTarget::Random => Ok(PayoutTarget::Random),
+            GenerateTarget::ToSelf => Ok(PayoutTarget::ToSelf),
+            GenerateTarget::Address => parse_address(input, network).map(PayoutTarget::Address),
+            GenerateTarget::Descriptor => parse_descriptor(input).and_then(|descriptor| {
+                u32::from_str(&self.autoblock_descriptor_index)
+                    .map_err(|e| Error::InvalidParameter(format!("start index: {}", e)))
+                    .map(|start_index| PayoutTarget::Descriptor {
+                        descriptor,
+                        start_index,
+                    })
+            }),
+            GenerateTarget::RoundRobin => {
+                parse_addresses(input, network).map(PayoutTarget::RoundRobin)
+            }
+        };
+        match payout {
+            Ok(payout) => Some(payout),
+            Err(e) => {
+                self.print(&e.report(&format!(
+                    "Invalid autoblock {} payout",
+                    self.autoblock_target
+                )));
+                None
+            }
+        }
+    }
+
+    pub fn start_auto_block(&mut self) {
+        log::info!("GUI.start_auto_block()");
+        if !self.autoblock_wip {
+            let interval = if let IntervalModel::Replay = self.autoblock_model {
+                match BlockInterval::from_file(&self.autoblock_replay_path) {
+                    Ok(interval) => interval,
+                    Err(e) => {
+                        self.print(&e);
+                        return;
+                    }
+                }
+            } else {
+                let tf_ms = match self.autoblocks_timeframe {
+                    TimeFrame::Second => 1_000,
+                    TimeFrame::Minute => 60_000,
+                };
+                let blocks = match u32::from_str(&self.autoblock_blocks) {
+                    Ok(blocks) if blocks > 0 => blocks,
+                    _ => {
+                        self.print("Invalid block count!");
+                        return;
+                    }
+                };
+                let delay = time::Duration::from_millis((tf_ms / blocks) as u64);
+                if let IntervalModel::Fixed = self.autoblock_model {
+                    BlockInterval::Fixed(delay)
+                } else {
+                    BlockInterval::Exponential(delay)
+                }
+            };
+            let payout = match self.autoblock_payout() {
+                Some(payout) => payout,
+                None => return,
+            };
+            self.autoblock_wip = true;
+            log::info!("start");
+            self.send_to_bitcoind(BitcoinMessage::StartAutoBlock(AutoBlock {
+                interval,
+                payout,
+                censor: self.censor_rules.clone(),
+            }));
+        }
+
+        if self.send_every_blocks_enabled {
+            self.toggle_every_blocks(true);
+        }
+    }
+
+    pub fn stop_auto_block(&mut self) {
+        if self.autoblock_wip {
+            self.send_to_bitcoind(BitcoinMessage::StopAutoBlock);
+        }
+    }
+
+    pub fn print(&mut self, msg: &str) {
+        let mut msg = msg.to_string();
+        if !msg.ends_with('\n') {
+            msg.push('\n');
+        }
+
+        self.console
+            .perform(Action::Edit(Edit::Paste(Arc::new(msg))));
+    }
+
+    pub fn auth_panel(&self) -> Container<Message> {
+        let address_input = {
+            let mut input =
+                TextInput::new("bitcoind address", &self.config.bitcoind.address).width(310);
+            if !self.connected {
+                input = input.on_input(Message::BitcoindAddress);
+            }
+            input
+        };
+
+        let (cookie, rpc_auth) = match (&self.config.bitcoind.auth_type, self.connected) {
+            (_, true) => (false, false),
+            (AuthMethod::RpcAuth, false) => (false, true),
+            (AuthMethod::Cookie, false) => (true, false),
+        };
+
+        let connect = if self.connected {
+            Self::button("Disconnect", Some(Message::Disconnect))
+        } else {
+            Self::button(
+                "Connect",
+                match (&self.config.bitcoind.auth_type, self.credentials_valid()) {
+                    (_, false) => None,
+                    (AuthMethod::RpcAuth, true) => Some(Message::ConnectRpcAuth),
+                    (AuthMethod::Cookie, true) => Some(Message::ConnectCookie),
+                },
+            )
+        };
+
+        let mut rpc_auth_check = Checkbox::new("", rpc_auth);
+        let mut cookie_check = Checkbox::new("", cookie);
+        if !self.connected {
+            rpc_auth_check = rpc_auth_check.on_toggle(Message::SelectRpcAuth);
+            cookie_check = cookie_check.on_toggle(Message::SelectCookie);
+        }
+
+        let chain_height = self
+            .block_height
+            .map(|height| Text::new(format!("Block height {} ({})", height, self.network())));
+
+        let health = match &self.health {
+            NodeHealth::Degraded { error, retry_in } if self.connected => Some(
+                Text::new(format!(
+                    "Connection degraded ({}), retrying in {}",
+                    error,
+                    Self::format_duration(*retry_in)
+                ))
+                .size(13)
+                .style(iced::Color::from_rgb(0.9, 0.5, 0.0)),
+            ),
+            _ => None,
+        };
+
+        let profiles = PickList::new(
+            self.config
+                .profiles
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<_>>(),
+            self.profile.clone(),
+            Message::Profile,
+        )
+        .placeholder("profile");
+        let (profiles, profile) = if self.connected {
+            let profile = self.profile.clone().unwrap_or_else(|| "-".to_string());
+            (None, Some(Text::new(profile)))
+        } else {
+            (Some(profiles), None)
+        };
+
+        let profile_input = {
+            let mut input = TextInput::new("profile name", &self.profile_name).width(150);
+            if !self.connected {
+                input = input.on_input(Message::ProfileName);
+            }
+            input
+        };
+
+        let col = Column::new()
+            .push(
+                Row::new()
+                    .push(Text::new("Profile: "))
+                    .push_maybe(profiles)
+                    .push_maybe(profile)
+                    .push(Space::with_width(Length::Fill))
+                    .push(profile_input)
+                    .push(Space::with_width(10))
+                    .push(Self::button(
+                        "Save as profile",
+                        if self.profile_name.trim().is_empty() {
+                            None
+                        } else {
+                            Some(Message::SaveProfile)
+                        },
+                    ))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
                 Row::new()
                     .push(Text::new("Bitcoind address: "))
                     .push(Space::with_width(Length::Fill))
@@ -568,14 +1636,74 @@ impl Gui {
                     })
                     .push(Space::with_width(10))
                     .push({
-                        let mut input =
-                            TextInput::new("password", &self.config.bitcoind.password).width(150);
-                        if rpc_auth {
+                        let typed = matches!(
+                            self.config.bitcoind.password_source,
+                            PasswordSource::Config | PasswordSource::NotSaved
+                        );
+                        let mut input = if typed {
+                            TextInput::new("password", &self.config.bitcoind.password).secure(true)
+                        } else {
+                            TextInput::new("", "")
+                        }
+                        .width(150);
+                        if rpc_auth && typed {
                             input = input.on_input(Message::Password);
                         }
                         input
                     }),
             )
+            .push_maybe(if rpc_auth {
+                Some(Space::with_height(5))
+            } else {
+                None
+            })
+            .push_maybe(if rpc_auth {
+                let source = PickList::new(
+                    vec![
+                        PasswordSource::Config,
+                        PasswordSource::NotSaved,
+                        PasswordSource::Env,
+                        PasswordSource::File,
+                    ],
+                    Some(&self.config.bitcoind.password_source),
+                    Message::PasswordSource,
+                );
+                let reference = match self.config.bitcoind.password_source {
+                    PasswordSource::Env => Some("variable name"),
+                    PasswordSource::File => Some("password file path"),
+                    _ => None,
+                }
+                .map(|placeholder| {
+                    TextInput::new(placeholder, &self.config.bitcoind.password_ref)
+                        .on_input(Message::PasswordRef)
+                        .width(310)
+                });
+                Some(
+                    Row::new()
+                        .push(Text::new("Password: "))
+                        .push(source)
+                        .push(Space::with_width(Length::Fill))
+                        .push_maybe(reference)
+                        .align_items(iced::alignment::Alignment::Center),
+                )
+            } else {
+                None
+            })
+            .push_maybe(match self.config.bitcoind.auth_type {
+                AuthMethod::RpcAuth => {
+                    Some(Text::new(self.config.bitcoind.password_origin()).size(13))
+                }
+                AuthMethod::Cookie => None,
+            })
+            .push_maybe(
+                (rpc_auth && self.config.bitcoind.password_source == PasswordSource::Config).then(
+                    || {
+                        Text::new("Warning: anyone reading minta.conf gets the RPC password")
+                            .size(13)
+                            .style(iced::Color::from_rgb(0.9, 0.5, 0.0))
+                    },
+                ),
+            )
             .push(Space::with_height(5))
             .push(
                 Row::new()
@@ -592,6 +1720,20 @@ impl Gui {
                         input
                     }),
             )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Text::new("Wallet: "))
+                    .push(Space::with_width(Length::Fill))
+                    .push({
+                        let mut input =
+                            TextInput::new("wallet name", &self.config.bitcoind.wallet).width(310);
+                        if !self.connected {
+                            input = input.on_input(Message::WalletName);
+                        }
+                        input
+                    }),
+            )
             .push(Space::with_height(10))
             .push(
                 Row::new()
@@ -604,6 +1746,8 @@ impl Gui {
                     .push(connect)
                     .push(Space::with_width(Length::Fill)),
             )
+            .push_maybe(health.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(health)
             .push_maybe(if chain_height.is_some() {
                 Some(Space::with_height(5))
             } else {
@@ -619,29 +1763,102 @@ impl Gui {
             } else {
                 None
             })
-            .push_maybe(chain_height);
+            .push_maybe(chain_height)
+            .push_maybe(self.signet_key_row());
 
         Container::new(col)
     }
 
     pub fn auto_block_panel(&self) -> Container<Message> {
-        let autoblock_btn = match (self.generate_wip, self.autoblock_wip, self.connected) {
-            (false, false, true) => Self::button("Generate", Some(Message::StartAutoblock)),
-            (false, true, true) => Self::button("Stop", Some(Message::StopAutoblock)),
+        let autoblock_btn = match (self.autoblock_wip, self.connected) {
+            (false, true) => Self::button("Generate", Some(Message::StartAutoblock)),
+            (true, true) => Self::button("Stop", Some(Message::StopAutoblock)),
             _ => Self::button("Generate", None),
         }
         .width(100);
 
-        let wip = self.generate_wip || self.autoblock_wip || !self.connected;
+        let wip = self.autoblock_wip || !self.connected;
+
+        let replay = self.autoblock_model == IntervalModel::Replay;
 
         let blocks_input = {
-            let mut input = TextInput::new("blocks", &self.autoblock_blocks).width(100);
-            if !wip {
+            let mut input = TextInput::new("blocks", &self.autoblock_blocks).width(60);
+            if !wip && !replay {
                 input = input.on_input(Message::AutoblockBlocks);
             }
             input
         };
 
+        let model_dropdown = PickList::new(
+            vec![
+                IntervalModel::Fixed,
+                IntervalModel::Exponential,
+                IntervalModel::Replay,
+            ],
+            Some(&self.autoblock_model),
+            Message::AutoblockModel,
+        );
+
+        let replay_input = if replay {
+            let mut input = TextInput::new(
+                "intervals file (seconds per line)",
+                &self.autoblock_replay_path,
+            );
+            if !wip {
+                input = input.on_input(Message::AutoblockReplayPath);
+            }
+            Some(input)
+        } else {
+            None
+        };
+
+        let target_dropdown = PickList::new(
+            vec![
+                GenerateTarget::Random,
+                GenerateTarget::ToSelf,
+                GenerateTarget::Address,
+                GenerateTarget::Descriptor,
+                GenerateTarget::RoundRobin,
+            ],
+            Some(&self.autoblock_target),
+            Message::AutoblockTarget,
+        );
+
+        let target_input = match self.autoblock_target {
+            GenerateTarget::Address | GenerateTarget::Descriptor | GenerateTarget::RoundRobin => {
+                let placeholder = match self.autoblock_target {
+                    GenerateTarget::Address => "address",
+                    GenerateTarget::Descriptor => "descriptor",
+                    _ => "addresses (comma or space separated)",
+                };
+                let mut input = TextInput::new(placeholder, &self.autoblock_target_input);
+                if !wip {
+                    input = input.on_input(Message::AutoblockTargetInput);
+                }
+                Some(input)
+            }
+            _ => None,
+        };
+
+        let index_input = if let GenerateTarget::Descriptor = self.autoblock_target {
+            let mut input =
+                TextInput::new("start index", &self.autoblock_descriptor_index).width(100);
+            if !wip {
+                input = input.on_input(Message::AutoblockDescriptorIndex);
+            }
+            Some(input)
+        } else {
+            None
+        };
+
+        let eta = self.next_block.as_ref().map(|(at, delay)| {
+            Text::new(format!(
+                "Next block at {} (in {:.1}s)",
+                at.format("%H:%M:%S"),
+                delay.as_secs_f64()
+            ))
+        });
+
         let tf_list = vec![TimeFrame::Second, TimeFrame::Minute];
 
         let dropdown = PickList::new(
@@ -650,22 +1867,39 @@ impl Gui {
             Message::AutoblockTimeframe,
         );
 
-        let col = Row::new()
-            .push(autoblock_btn)
-            .push(Space::with_width(10))
-            .push(blocks_input)
-            .push(Text::new(" blocks every "))
-            .push(dropdown)
-            .align_items(iced::alignment::Alignment::Center);
+        let col = Column::new()
+            .push(
+                Row::new()
+                    .push(autoblock_btn)
+                    .push(Space::with_width(10))
+                    .push(blocks_input)
+                    .push(Text::new(" blocks every "))
+                    .push(dropdown)
+                    .push(Space::with_width(10))
+                    .push(model_dropdown)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push_maybe(replay_input.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(replay_input)
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Text::new("Pay to "))
+                    .push(target_dropdown)
+                    .push(Space::with_width(5))
+                    .push_maybe(target_input)
+                    .push(Space::with_width(5))
+                    .push_maybe(index_input)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push_maybe(eta.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(eta);
 
         Container::new(col)
     }
 
     pub fn generate_panel(&self) -> Container<Message> {
-        let generate_signal = match (
-            &self.generate_target,
-            self.generate_wip || !self.connected || self.autoblock_wip,
-        ) {
+        let generate_signal = match (&self.generate_target, !self.connected || self.autoblock_wip) {
             (GenerateTarget::Address, false) => Some(Message::GenerateToAddress),
             (GenerateTarget::ToSelf, false) => Some(Message::GenerateToSelf),
             (GenerateTarget::Random, false) => Some(Message::GenerateToRandom),
@@ -675,7 +1909,7 @@ impl Gui {
 
         let generate_button = Self::button("Generate", generate_signal).width(100);
 
-        let blocks_signal = if !self.generate_wip && self.connected {
+        let blocks_signal = if self.connected {
             Some(Message::BlocksGenerate)
         } else {
             None
@@ -692,7 +1926,7 @@ impl Gui {
         let index_input = if let GenerateTarget::Descriptor = self.generate_target {
             let mut input =
                 TextInput::new("start index", &self.generate_descriptor_index).width(100);
-            if !self.generate_wip && self.connected {
+            if self.connected {
                 input = input.on_input(Message::DescriptorIndexGenerate);
             }
             Some(input)
@@ -700,13 +1934,13 @@ impl Gui {
             None
         };
 
-        let address_signal = if !self.generate_wip && self.connected {
+        let address_signal = if self.connected {
             Some(Message::AddressGenerate)
         } else {
             None
         };
 
-        let descriptor_signal = if !self.generate_wip && self.connected {
+        let descriptor_signal = if self.connected {
             Some(Message::DescriptorGenerate)
         } else {
             None
@@ -771,19 +2005,23 @@ impl Gui {
                 )
             } else {
                 None
-            });
+            })
+            .push_maybe(self.job_progress(|kind| matches!(kind, JobKind::GenerateToDescriptor(_))));
 
         Container::new(col)
     }
 
     pub fn invalidate_panel(&self) -> Container<Message> {
         let blocks = u64::from_str(&self.reorg_blocks);
-        let reorg_signal =
-            if !self.autoblock_wip && !self.generate_wip && blocks.is_ok() && self.connected {
-                Some(Message::Invalidate)
-            } else {
-                None
-            };
+        let reorg_signal = if !self.autoblock_wip
+            && self.running_jobs() == 0
+            && blocks.is_ok()
+            && self.connected
+        {
+            Some(Message::Invalidate)
+        } else {
+            None
+        };
         let mut input = TextInput::new("", &self.reorg_blocks).width(75);
         if (reorg_signal.is_some() || self.reorg_blocks.is_empty()) && self.connected {
             input = input.on_input(Message::InvalidateBlocks);
@@ -830,11 +2068,42 @@ impl Gui {
     }
 
     pub fn send_panel(&self) -> Container<Message> {
-        let balance = self
-            .balance
-            .map(|balance| Text::new(format!("Balance: {}", balance)));
+        let balance = self.balance.as_ref().map(|balance| {
+            Text::new(format!(
+                "Balance: {} (pending: {}, immature: {})",
+                balance.trusted, balance.untrusted_pending, balance.immature
+            ))
+        });
+
+        let bootstrap = if self.can_mine() {
+            let mut input = TextInput::new("amount", &self.bootstrap_amount).width(100);
+            if self.connected {
+                input = input.on_input(Message::BootstrapAmount);
+            }
+            Some(
+                Row::new()
+                    .push(
+                        Self::button(
+                            "Bootstrap",
+                            if self.connected && !self.bootstrap_amount.is_empty() {
+                                Some(Message::Bootstrap)
+                            } else {
+                                None
+                            },
+                        )
+                        .width(100),
+                    )
+                    .push(Space::with_width(10))
+                    .push(Text::new(" mine to self until "))
+                    .push(input)
+                    .push(Text::new(" BTC are spendable"))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+        } else {
+            None
+        };
 
-        let enable = !self.send_wip && self.connected;
+        let enable = self.connected;
 
         let send_address_btn = Self::button(
             "Send",
@@ -862,7 +2131,7 @@ impl Gui {
             input
         };
 
-        let enable = !self.send_wip && self.connected && !self.send_every_blocks_enabled;
+        let enable = self.connected && !self.send_every_blocks_enabled;
         let send_descriptor_btn = Self::button(
             "Send",
             if enable {
@@ -924,7 +2193,7 @@ impl Gui {
             let mut input = TextInput::new("blocks", &self.send_every_blocks).width(120);
             let enable = match self.autoblock_wip {
                 true => !self.send_every_blocks_enabled,
-                false => self.send_every_blocks_enabled && !self.send_wip,
+                false => self.send_every_blocks_enabled,
             };
             let enable = enable && self.connected;
             if enable {
@@ -935,6 +2204,8 @@ impl Gui {
 
         let col = Column::new()
             .push_maybe(balance)
+            .push_maybe(bootstrap.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(bootstrap)
             .push(Space::with_height(5))
             .push(Rule::horizontal(5))
             .push(Space::with_height(5))
@@ -971,6 +2242,7 @@ impl Gui {
                     .push(descriptor_index_input)
                     .align_items(iced::alignment::Alignment::Center),
             )
+            .push_maybe(self.job_progress(|kind| matches!(kind, JobKind::SendToDescriptor(_))))
             .push(Space::with_height(5))
             .push(
                 Row::new()
@@ -986,24 +2258,1268 @@ impl Gui {
         Container::new(col)
     }
 
-    pub fn console_panel(&self) -> Container<Message> {
-        let console = TextEditor::new(&self.console).on_action(Message::ConsoleEdit);
-
-        Container::new(scrollable(console).height(300))
+    pub fn network(&self) -> Network {
+        self.network.unwrap_or(Network::Regtest)
     }
 
-    pub fn u32_checked(input: String, output: &mut String, max: u32) {
-        if let Ok(blocks) = u32::from_str(&input) {
-            if blocks <= max {
-                *output = input;
-            }
-        } else if input.is_empty() {
-            *output = input;
+    /// Blocks can be generated w/ `generatetoaddress` on regtest, or signed w/ the
+    /// challenge key on signet.
+    pub fn can_mine(&self) -> bool {
+        match self.network() {
+            Network::Regtest => true,
+            Network::Signet => self.signet_key_set,
+            _ => false,
         }
     }
-    pub fn amount_checked(input: String, output: &mut String) {
-        if Amount::from_str_in(&input, miniscript::bitcoin::Denomination::Bitcoin).is_ok()
-            || input.is_empty()
+
+    pub fn can_mock_time(&self) -> bool {
+        // setmocktime is regtest only
+        self.network() == Network::Regtest
+    }
+
+    fn signet_key_row(&self) -> Option<Row<Message>> {
+        if !self.connected || self.network() != Network::Signet {
+            return None;
+        }
+        let status = if self.signet_key_set {
+            "Signet key: set"
+        } else {
+            "Signet key: "
+        };
+        Some(
+            Row::new()
+                .push(Text::new(status))
+                .push(Space::with_width(Length::Fill))
+                .push(
+                    TextInput::new("challenge private key (WIF)", &self.signet_key)
+                        .on_input(Message::SignetKey)
+                        .secure(true)
+                        .width(310),
+                )
+                .push(Space::with_width(10))
+                .push(Self::button(
+                    "Set",
+                    if self.signet_key.trim().is_empty() {
+                        None
+                    } else {
+                        Some(Message::SetSignetKey)
+                    },
+                ))
+                .align_items(iced::alignment::Alignment::Center),
+        )
+    }
+
+    pub fn main_tab(&self) -> Container<Message> {
+        let col = if self.can_mine() {
+            Column::new()
+                .push(self.auto_block_panel())
+                .push(Space::with_height(5))
+                .push(Rule::horizontal(4))
+                .push(Space::with_height(5))
+                .push(self.generate_panel())
+        } else if self.network() == Network::Signet {
+            Column::new().push(Text::new(
+                "Set the signet challenge key in the connection panel to generate blocks.",
+            ))
+        } else {
+            Column::new().push(Text::new(format!(
+                "Block generation is not available on {}, blocks are produced by the network.",
+                self.network()
+            )))
+        };
+        let col = col
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(4))
+            .push(Space::with_height(5))
+            .push(self.invalidate_panel())
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(4))
+            .push(Space::with_height(5))
+            .push(self.address_panel())
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(4))
+            .push(Space::with_height(5))
+            .push(self.send_panel());
+
+        Container::new(col)
+    }
+
+    pub fn tab_panel(&self) -> Container<Message> {
+        let tabs = PickList::new(
+            vec![
+                Tab::Main,
+                Tab::External,
+                Tab::Psbt,
+                Tab::RawTx,
+                Tab::BlockBuilder,
+                Tab::Censorship,
+                Tab::Scheduler,
+                Tab::Actors,
+                Tab::Timelock,
+                Tab::Time,
+                Tab::Jobs,
+                Tab::Wallets,
+            ],
+            Some(&self.tab),
+            Message::Tab,
+        );
+
+        let running = self.running_jobs();
+        let running = if running > 0 {
+            Some(Text::new(format!("{} job(s) running", running)))
+        } else {
+            None
+        };
+
+        let row = Row::new()
+            .push(Text::new("Panel: "))
+            .push(Space::with_width(10))
+            .push(tabs)
+            .push(Space::with_width(Length::Fill))
+            .push_maybe(running)
+            .align_items(iced::alignment::Alignment::Center);
+
+        Container::new(row)
+    }
+
+    pub fn wallets_panel(&self) -> Container<Message> {
+        let enable = self.connected;
+
+        let selector = PickList::new(
+            self.wallets_loaded.clone(),
+            self.wallet_active.clone(),
+            Message::SelectWallet,
+        )
+        .placeholder("wallet");
+
+        let btn = |label: &str, msg: Message, enable: bool| {
+            Self::button(label, if enable { Some(msg) } else { None }).width(100)
+        };
+
+        let input = |placeholder: &str, value: &str, msg: fn(String) -> Message| {
+            let mut input = TextInput::new(placeholder, value);
+            if enable {
+                input = input.on_input(msg);
+            }
+            input
+        };
+
+        let mut col = Column::new()
+            .push(
+                Row::new()
+                    .push(Text::new("Selected wallet: "))
+                    .push(selector)
+                    .push(Space::with_width(Length::Fill))
+                    .push(btn("Refresh", Message::RefreshWallets, enable))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Unlock", Message::UnlockWallet, enable))
+                    .push(Space::with_width(10))
+                    .push(
+                        input(
+                            "passphrase",
+                            &self.unlock_passphrase,
+                            Message::UnlockPassphrase,
+                        )
+                        .secure(true),
+                    )
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn(
+                        "Create",
+                        Message::CreateWallet,
+                        enable && !self.new_wallet_name.trim().is_empty(),
+                    ))
+                    .push(Space::with_width(10))
+                    .push(input("name", &self.new_wallet_name, Message::NewWalletName))
+                    .push(Space::with_width(10))
+                    .push(
+                        input(
+                            "passphrase (optional)",
+                            &self.new_wallet_passphrase,
+                            Message::NewWalletPassphrase,
+                        )
+                        .secure(true),
+                    )
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(
+                        Checkbox::new("descriptor wallet", self.new_wallet_descriptors)
+                            .on_toggle_maybe(if enable {
+                                Some(Message::ToggleNewWalletDescriptors)
+                            } else {
+                                None
+                            }),
+                    )
+                    .push(Space::with_width(20))
+                    .push(
+                        Checkbox::new("private keys", self.new_wallet_private_keys)
+                            .on_toggle_maybe(if enable {
+                                Some(Message::ToggleNewWalletPrivateKeys)
+                            } else {
+                                None
+                            }),
+                    ),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5));
+
+        for wallet in &self.wallets_loaded {
+            let active = self.wallet_active.as_ref() == Some(wallet);
+            col = col.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(Text::new(wallet.clone()).width(Length::Fill))
+                    .push(Text::new(if active { "selected" } else { "loaded" }))
+                    .push(Space::with_width(10))
+                    .push(btn(
+                        "Unload",
+                        Message::UnloadWallet(wallet.clone()),
+                        enable && !active,
+                    ))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+        for wallet in &self.wallets_available {
+            col = col.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(Text::new(wallet.clone()).width(Length::Fill))
+                    .push(Text::new("not loaded"))
+                    .push(Space::with_width(10))
+                    .push(btn("Load", Message::LoadWallet(wallet.clone()), enable))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+
+        Container::new(scrollable(col).height(Length::Fill))
+    }
+
+    pub fn external_panel(&self) -> Container<Message> {
+        let enable = self.connected && !self.external_wip;
+
+        let descriptor_input = {
+            let mut input = TextInput::new("private descriptor", &self.external_descriptor);
+            if enable && !self.external_loaded {
+                input = input.on_input(Message::ExternalDescriptor);
+            }
+            input
+        };
+
+        let load_btn = Self::button(
+            "Load",
+            if enable && !self.external_loaded {
+                Some(Message::LoadExternal)
+            } else {
+                None
+            },
+        )
+        .width(100);
+
+        let balance = self
+            .external_balance
+            .filter(|_| self.external_loaded)
+            .map(|balance| Text::new(format!("External balance: {}", balance)));
+
+        let enable = enable && self.external_loaded;
+
+        let amount_input = {
+            let mut input = TextInput::new("amount", &self.external_amount).width(100);
+            if enable {
+                input = input.on_input(Message::ExternalAmount);
+            }
+            input
+        };
+
+        let address_input = {
+            let mut input = TextInput::new("address", &self.external_address).width(Length::Fill);
+            if enable {
+                input = input.on_input(Message::ExternalAddress);
+            }
+            input
+        };
+
+        let send_btn = Self::button(
+            "Send",
+            if enable {
+                Some(Message::ExternalSendToAddress)
+            } else {
+                None
+            },
+        )
+        .width(100);
+
+        let send_back_btn = Self::button(
+            "Send back",
+            if enable {
+                Some(Message::ExternalSendToSelf)
+            } else {
+                None
+            },
+        )
+        .width(100);
+
+        let sweep_btn = Self::button(
+            "Sweep",
+            if enable {
+                Some(Message::ExternalSweep)
+            } else {
+                None
+            },
+        )
+        .width(100);
+
+        let schedule_enable = enable && !self.external_back_enabled;
+
+        let back_checkbox =
+            Checkbox::new("", self.external_back_enabled).on_toggle_maybe(if enable {
+                Some(Message::ToggleSendBack)
+            } else {
+                None
+            });
+
+        let sweep_checkbox =
+            Checkbox::new("sweep", self.external_back_sweep).on_toggle_maybe(if schedule_enable {
+                Some(Message::ToggleSendBackSweep)
+            } else {
+                None
+            });
+
+        let min_input = {
+            let mut input = TextInput::new("min", &self.external_back_min);
+            if schedule_enable && !self.external_back_sweep {
+                input = input.on_input(Message::ExternalBackMin);
+            }
+            input
+        };
+
+        let max_input = {
+            let mut input = TextInput::new("max", &self.external_back_max);
+            if schedule_enable && !self.external_back_sweep {
+                input = input.on_input(Message::ExternalBackMax);
+            }
+            input
+        };
+
+        let blocks_input = {
+            let mut input = TextInput::new("blocks", &self.external_back_blocks).width(80);
+            if schedule_enable {
+                input = input.on_input(Message::ExternalBackBlocks);
+            }
+            input
+        };
+
+        let col = Column::new()
+            .push(
+                Row::new()
+                    .push(descriptor_input)
+                    .push(Space::with_width(10))
+                    .push(load_btn)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push_maybe(balance)
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(send_btn)
+                    .push(Space::with_width(10))
+                    .push(amount_input)
+                    .push(Text::new(" BTC to "))
+                    .push(address_input)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(send_back_btn)
+                    .push(Space::with_width(10))
+                    .push(sweep_btn)
+                    .push(Space::with_width(Length::Fill))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(back_checkbox)
+                    .push(Text::new(" send back "))
+                    .push(min_input)
+                    .push(Text::new(" - "))
+                    .push(max_input)
+                    .push(Text::new(" BTC "))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Text::new(" every "))
+                    .push(blocks_input)
+                    .push(Text::new(" blocks "))
+                    .push(Space::with_width(10))
+                    .push(sweep_checkbox)
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+
+        Container::new(col)
+    }
+
+    pub fn psbt_panel(&self) -> Container<Message> {
+        let enable = self.connected && !self.psbt_wip;
+        let valid = enable && !self.psbt.is_empty();
+
+        let psbt_input = {
+            let mut input = TextInput::new("base64 psbt", &self.psbt);
+            if enable {
+                input = input.on_input(Message::Psbt);
+            }
+            input
+        };
+
+        let others_input = {
+            let mut input = TextInput::new("other psbts to combine", &self.psbt_others);
+            if enable {
+                input = input.on_input(Message::PsbtOthers);
+            }
+            input
+        };
+
+        let btn = |label: &str, msg: Message| {
+            Self::button(label, if valid { Some(msg) } else { None }).width(90)
+        };
+
+        let col = Column::new()
+            .push(psbt_input)
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Decode", Message::DecodePsbt))
+                    .push(Space::with_width(5))
+                    .push(btn("Sign", Message::SignPsbt))
+                    .push(Space::with_width(5))
+                    .push(btn("Finalize", Message::FinalizePsbt))
+                    .push(Space::with_width(5))
+                    .push(btn("Broadcast", Message::BroadcastPsbt))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Combine", Message::CombinePsbt))
+                    .push(Space::with_width(10))
+                    .push(others_input)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push_maybe(self.psbt_summary.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(self.psbt_summary.as_ref().map(|_| Rule::horizontal(5)))
+            .push_maybe(self.psbt_summary.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(
+                self.psbt_summary
+                    .as_ref()
+                    .map(|summary| Text::new(summary.clone()).size(13)),
+            );
+
+        Container::new(col)
+    }
+
+    pub fn raw_tx_panel(&self) -> Container<Message> {
+        let enable = self.connected && !self.raw_tx_wip;
+        let valid = enable && !self.raw_tx.is_empty();
+
+        let raw_tx_input = {
+            let mut input = TextInput::new("raw transaction hex", &self.raw_tx);
+            if enable {
+                input = input.on_input(Message::RawTx);
+            }
+            input
+        };
+
+        let btn = |label: &str, msg: Message| {
+            Self::button(label, if valid { Some(msg) } else { None }).width(100)
+        };
+
+        let mine_checkbox = Checkbox::new("mine a block", self.raw_tx_mine).on_toggle_maybe(
+            if enable && self.can_mine() {
+                Some(Message::ToggleRawTxMine)
+            } else {
+                None
+            },
+        );
+
+        let col = Column::new()
+            .push(raw_tx_input)
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Decode", Message::DecodeRawTx))
+                    .push(Space::with_width(5))
+                    .push(btn("Test", Message::TestRawTx))
+                    .push(Space::with_width(5))
+                    .push(btn("Broadcast", Message::BroadcastRawTx))
+                    .push(Space::with_width(10))
+                    .push(mine_checkbox)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push_maybe(self.raw_tx_summary.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(self.raw_tx_summary.as_ref().map(|_| Rule::horizontal(5)))
+            .push_maybe(self.raw_tx_summary.as_ref().map(|_| Space::with_height(5)))
+            .push_maybe(
+                self.raw_tx_summary
+                    .as_ref()
+                    .map(|summary| Text::new(summary.clone()).size(13)),
+            );
+
+        Container::new(col)
+    }
+
+    pub fn block_builder_panel(&self) -> Container<Message> {
+        // generateblock is regtest only
+        let enable = self.connected && !self.block_wip && self.network() == Network::Regtest;
+
+        let target = PickList::new(
+            vec![
+                GenerateTarget::Random,
+                GenerateTarget::ToSelf,
+                GenerateTarget::Address,
+            ],
+            Some(&self.block_target),
+            Message::BlockTarget,
+        );
+        let address_input = match self.block_target {
+            GenerateTarget::Address => {
+                let mut input = TextInput::new("coinbase address", &self.block_address);
+                if enable {
+                    input = input.on_input(Message::BlockAddress);
+                }
+                Some(input)
+            }
+            _ => None,
+        };
+
+        let raw_txs_input = {
+            let mut input = TextInput::new(
+                "raw transactions hex (space separated, included after the mempool ones)",
+                &self.block_raw_txs,
+            );
+            if enable {
+                input = input.on_input(Message::BlockRawTxs);
+            }
+            input
+        };
+
+        let all_selected =
+            !self.mempool.is_empty() && self.block_selected.len() == self.mempool.len();
+        let select_all = Checkbox::new(
+            format!(
+                "{} / {} mempool transaction(s) selected",
+                self.block_selected.len(),
+                self.mempool.len()
+            ),
+            all_selected,
+        )
+        .on_toggle_maybe(if enable {
+            Some(Message::SelectAllMempool)
+        } else {
+            None
+        });
+
+        let mut mempool = Column::new();
+        for tx in &self.mempool {
+            let txid = tx.txid;
+            mempool = mempool.push(Space::with_height(3)).push(
+                Checkbox::new(
+                    format!(
+                        "{} {} vB {:.1} sat/vB{}",
+                        tx.txid,
+                        tx.vsize,
+                        tx.fee_rate(),
+                        if tx.ancestors > 1 {
+                            format!(" ({} unconfirmed ancestors)", tx.ancestors - 1)
+                        } else {
+                            String::new()
+                        }
+                    ),
+                    self.block_selected.contains(&txid),
+                )
+                .text_size(13)
+                .on_toggle_maybe(if enable {
+                    Some(move |selected| Message::ToggleBlockTx(txid, selected))
+                } else {
+                    None
+                }),
+            );
+        }
+
+        let col = Column::new()
+            .push(
+                Row::new()
+                    .push(Self::button(
+                        "Mine block",
+                        if enable {
+                            Some(Message::BuildBlock)
+                        } else {
+                            None
+                        },
+                    ))
+                    .push(Space::with_width(10))
+                    .push(Text::new(" paying to "))
+                    .push(target)
+                    .push(Space::with_width(10))
+                    .push_maybe(address_input)
+                    .push(Space::with_width(Length::Fill))
+                    .push(Self::button(
+                        "Refresh",
+                        if self.connected {
+                            Some(Message::RefreshMempool)
+                        } else {
+                            None
+                        },
+                    ))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(raw_txs_input)
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(select_all)
+            .push(scrollable(mempool).height(Length::Fill));
+
+        Container::new(col)
+    }
+
+    pub fn actors_panel(&self) -> Container<Message> {
+        let enable = self.connected;
+
+        let input = |placeholder: &str, value: &str, msg: fn(String) -> Message| {
+            let mut input = TextInput::new(placeholder, value);
+            if enable {
+                input = input.on_input(msg);
+            }
+            input
+        };
+
+        let kind = PickList::new(
+            vec![
+                ActorKind::Exchange,
+                ActorKind::Retail,
+                ActorKind::Consolidator,
+            ],
+            Some(&self.actor_kind),
+            Message::ActorKind,
+        );
+        let profile = match self.actor_kind {
+            ActorKind::Exchange => Row::new()
+                .push(input("count", &self.actor_outputs, Message::ActorOutputs).width(80))
+                .push(Text::new(" withdrawals every "))
+                .push(input("blocks", &self.actor_blocks, Message::ActorBlocks).width(80))
+                .push(Text::new(" blocks")),
+            ActorKind::Retail => Row::new()
+                .push(input("rate", &self.actor_rate, Message::ActorRate).width(80))
+                .push(Text::new(" tx/hour")),
+            ActorKind::Consolidator => Row::new()
+                .push(Text::new("consolidate from "))
+                .push(input("count", &self.actor_utxos, Message::ActorUtxos).width(80))
+                .push(Text::new(" coins")),
+        }
+        .align_items(iced::alignment::Alignment::Center);
+        let amounts = match self.actor_kind {
+            ActorKind::Consolidator => None,
+            _ => Some(
+                Row::new()
+                    .push(Text::new("pays between "))
+                    .push(input("min", &self.actor_min, Message::ActorMin).width(100))
+                    .push(Text::new(" and "))
+                    .push(input("max", &self.actor_max, Message::ActorMax).width(100))
+                    .push(Text::new(" BTC, "))
+                    .align_items(iced::alignment::Alignment::Center),
+            ),
+        };
+
+        let mut actors = Column::new();
+        for actor in &self.actors {
+            actors = actors.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(Text::new(actor.to_string()).width(Length::Fill))
+                    .push(Space::with_width(10))
+                    .push(Self::button("Remove", Some(Message::RemoveActor(actor.id))).width(80))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+
+        let col = Column::new()
+            .push(Text::new(
+                "Each actor is a separate wallet, playing its profile after every autoblock block",
+            ))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(input("name", &self.actor_name, Message::ActorName).width(150))
+                    .push(Space::with_width(10))
+                    .push(kind)
+                    .push(Space::with_width(10))
+                    .push(profile)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Self::button(
+                        "Add",
+                        if enable {
+                            Some(Message::AddActor)
+                        } else {
+                            None
+                        },
+                    ))
+                    .push(Space::with_width(10))
+                    .push_maybe(amounts)
+                    .push(Text::new("funded w/ "))
+                    .push(input("amount", &self.actor_funding, Message::ActorFunding).width(100))
+                    .push(Text::new(" BTC"))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(scrollable(actors).height(Length::Fill));
+
+        Container::new(col)
+    }
+
+    pub fn scheduler_panel(&self) -> Container<Message> {
+        let enable = self.connected;
+
+        let input = |placeholder: &str, value: &str, msg: fn(String) -> Message| {
+            let mut input = TextInput::new(placeholder, value);
+            if enable {
+                input = input.on_input(msg);
+            }
+            input
+        };
+
+        let target = PickList::new(
+            vec![
+                GenerateTarget::RoundRobin,
+                GenerateTarget::Descriptor,
+                GenerateTarget::Random,
+            ],
+            Some(&self.schedule_target),
+            Message::ScheduleTarget,
+        );
+        let target_input = match self.schedule_target {
+            GenerateTarget::RoundRobin => Some(input(
+                "addresses (comma or space separated)",
+                &self.schedule_input,
+                Message::ScheduleInput,
+            )),
+            GenerateTarget::Descriptor => Some(input(
+                "descriptor",
+                &self.schedule_input,
+                Message::ScheduleInput,
+            )),
+            _ => None,
+        };
+        let index_input = match self.schedule_target {
+            GenerateTarget::Descriptor => {
+                Some(input("start index", &self.schedule_index, Message::ScheduleIndex).width(100))
+            }
+            _ => None,
+        };
+        let unit = PickList::new(
+            vec![
+                FrequencyUnit::Blocks,
+                FrequencyUnit::Seconds,
+                FrequencyUnit::Minutes,
+            ],
+            Some(&self.schedule_unit),
+            Message::ScheduleUnit,
+        );
+
+        let mut schedules = Column::new();
+        for schedule in &self.schedules {
+            schedules = schedules.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(Text::new(schedule.to_string()).width(Length::Fill))
+                    .push(Space::with_width(10))
+                    .push(
+                        Self::button("Remove", Some(Message::RemoveSchedule(schedule.id)))
+                            .width(80),
+                    )
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+
+        let col = Column::new()
+            .push(
+                Row::new()
+                    .push(Text::new("Send to "))
+                    .push(target)
+                    .push(Space::with_width(10))
+                    .push_maybe(target_input)
+                    .push(Space::with_width(10))
+                    .push_maybe(index_input)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Self::button(
+                        "Add",
+                        if enable {
+                            Some(Message::AddSchedule)
+                        } else {
+                            None
+                        },
+                    ))
+                    .push(Space::with_width(10))
+                    .push(Text::new(" between "))
+                    .push(input("min", &self.schedule_min, Message::ScheduleMin).width(100))
+                    .push(Text::new(" and "))
+                    .push(input("max", &self.schedule_max, Message::ScheduleMax).width(100))
+                    .push(Text::new(" BTC every "))
+                    .push(input("count", &self.schedule_every, Message::ScheduleEvery).width(80))
+                    .push(Space::with_width(5))
+                    .push(unit)
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(scrollable(schedules).height(Length::Fill));
+
+        Container::new(col)
+    }
+
+    pub fn censorship_panel(&self) -> Container<Message> {
+        // censorship relies on generateblock, regtest only
+        let enable = self.network() == Network::Regtest;
+
+        let kind = PickList::new(
+            vec![
+                CensorKind::FeeRate,
+                CensorKind::Descriptor,
+                CensorKind::Txids,
+            ],
+            Some(&self.censor_kind),
+            Message::CensorKind,
+        );
+        let placeholder = match self.censor_kind {
+            CensorKind::Descriptor => "descriptor",
+            CensorKind::FeeRate => "sat/vB",
+            CensorKind::Txids => "txids (comma or space separated)",
+        };
+        let mut input = TextInput::new(placeholder, &self.censor_input);
+        let mut blocks = TextInput::new("blocks", &self.censor_blocks).width(80);
+        if enable {
+            input = input.on_input(Message::CensorInput);
+            blocks = blocks.on_input(Message::CensorBlocks);
+        }
+
+        let mut rules = Column::new();
+        for (index, rule) in self.censor_rules.iter().enumerate() {
+            rules = rules.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(Text::new(format!("Exclude txs {}", rule)).width(Length::Fill))
+                    .push(Space::with_width(10))
+                    .push(Self::button("Remove", Some(Message::RemoveCensorRule(index))).width(80))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+
+        let col = Column::new()
+            .push(Text::new(
+                "Transactions matching a rule (and their descendants) are excluded from the next \
+                 autoblock blocks.",
+            ))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Self::button(
+                        "Add rule",
+                        if enable && !self.censor_input.trim().is_empty() {
+                            Some(Message::AddCensorRule)
+                        } else {
+                            None
+                        },
+                    ))
+                    .push(Space::with_width(10))
+                    .push(Text::new(" exclude txs "))
+                    .push(kind)
+                    .push(Space::with_width(10))
+                    .push(input)
+                    .push(Space::with_width(10))
+                    .push(Text::new(" for "))
+                    .push(blocks)
+                    .push(Text::new(" blocks"))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(scrollable(rules).height(Length::Fill));
+
+        Container::new(col)
+    }
+
+    pub fn timelock_panel(&self) -> Container<Message> {
+        let enable = self.connected && !self.autoblock_wip && self.can_mine();
+
+        let input = |placeholder: &str, value: &str, msg: fn(String) -> Message| {
+            let mut input = TextInput::new(placeholder, value);
+            if enable {
+                input = input.on_input(msg);
+            }
+            input
+        };
+
+        let btn =
+            |msg: Message| Self::button("Mine", if enable { Some(msg) } else { None }).width(100);
+
+        let median_time = self.median_time.map(|time| {
+            Text::new(format!(
+                "Median time past: {} ({} UTC)",
+                time,
+                Self::format_timestamp(time)
+            ))
+        });
+
+        let col = Column::new()
+            .push_maybe(median_time)
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn(Message::MineUntilHeight))
+                    .push(Space::with_width(10))
+                    .push(Text::new(" until height "))
+                    .push(
+                        input("height", &self.timelock_height, Message::TimelockHeight).width(100),
+                    )
+                    .push(Space::with_width(Length::Fill))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn(Message::MineUntilConfirmed))
+                    .push(Space::with_width(10))
+                    .push(Text::new(" until "))
+                    .push(
+                        input(
+                            "confs",
+                            &self.timelock_confirmations,
+                            Message::TimelockConfirmations,
+                        )
+                        .width(60),
+                    )
+                    .push(Text::new(" confirmations"))
+                    .push(Space::with_width(Length::Fill))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(input(
+                "<txid>:<vout>",
+                &self.timelock_outpoint,
+                Message::TimelockOutpoint,
+            ))
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(
+                        Self::button(
+                            "Mine",
+                            if enable && self.can_mock_time() {
+                                Some(Message::AdvanceMtp)
+                            } else {
+                                None
+                            },
+                        )
+                        .width(100),
+                    )
+                    .push(Space::with_width(10))
+                    .push(Text::new(" until MTP "))
+                    .push(input(
+                        "unix time or YYYY-MM-DD HH:MM:SS",
+                        &self.timelock_timestamp,
+                        Message::TimelockTimestamp,
+                    ))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+
+        Container::new(col)
+    }
+
+    pub fn time_panel(&self) -> Container<Message> {
+        let enable = self.connected && self.can_mock_time();
+
+        let mock_time = match self.mock_time {
+            Some(time) => format!("Mock time: {} ({} UTC)", time, Self::format_timestamp(time)),
+            None => "Mock time: disabled".to_string(),
+        };
+
+        let time_input = {
+            let mut input =
+                TextInput::new("unix time or YYYY-MM-DD HH:MM:SS", &self.mock_time_input);
+            if enable {
+                input = input.on_input(Message::MockTime);
+            }
+            input
+        };
+
+        let jump_input = {
+            let mut input = TextInput::new("count", &self.mock_time_jump).width(80);
+            if enable {
+                input = input.on_input(Message::MockTimeJump);
+            }
+            input
+        };
+
+        let units = PickList::new(
+            vec![JumpUnit::Hour, JumpUnit::Day, JumpUnit::Week],
+            Some(&self.mock_time_jump_unit),
+            Message::MockTimeJumpUnit,
+        );
+
+        let step_checkbox =
+            Checkbox::new("", self.mock_time_step_enabled).on_toggle_maybe(if enable {
+                Some(Message::ToggleMockTimeStep)
+            } else {
+                None
+            });
+
+        let step_input = {
+            let mut input = TextInput::new("seconds", &self.mock_time_step).width(80);
+            if enable && !self.mock_time_step_enabled {
+                input = input.on_input(Message::MockTimeStep);
+            }
+            input
+        };
+
+        let btn = |label: &str, msg: Message| {
+            Self::button(label, if enable { Some(msg) } else { None }).width(100)
+        };
+
+        let col = Column::new()
+            .push(Text::new(mock_time))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Set", Message::SetMockTime))
+                    .push(Space::with_width(10))
+                    .push(time_input)
+                    .push(Space::with_width(10))
+                    .push(btn("Reset", Message::ResetMockTime))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(btn("Jump", Message::JumpMockTime))
+                    .push(Space::with_width(10))
+                    .push(jump_input)
+                    .push(Space::with_width(10))
+                    .push(units)
+                    .push(Text::new(" forward"))
+                    .push(Space::with_width(Length::Fill))
+                    .align_items(iced::alignment::Alignment::Center),
+            )
+            .push(Space::with_height(5))
+            .push(Rule::horizontal(5))
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(step_checkbox)
+                    .push(Text::new(" advance mock time "))
+                    .push(step_input)
+                    .push(Text::new(" seconds per autoblock block"))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+
+        Container::new(col)
+    }
+
+    pub fn running_jobs(&self) -> usize {
+        self.jobs
+            .values()
+            .filter(|job| job.status == JobStatus::Running)
+            .count()
+    }
+
+    pub fn format_duration(duration: time::Duration) -> String {
+        let secs = duration.as_secs();
+        match secs {
+            0..=59 => format!("{}s", secs),
+            60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+            _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+        }
+    }
+
+    pub fn job_details(job: &JobInfo) -> String {
+        let mut details = format!("{}/{}", job.done, job.total);
+        if let Some(index) = job.index {
+            details.push_str(&format!(" index: {}", index));
+        }
+        if let Some(txid) = job.last_txid {
+            details.push_str(&format!(" last txid: {}", txid));
+        }
+        if let (Some(eta), JobStatus::Running) = (job.eta, &job.status) {
+            details.push_str(&format!(" ETA: {}", Self::format_duration(eta)));
+        }
+        details
+    }
+
+    /// Progress bar, details & cancel button of the last running job matching `filter`.
+    pub fn job_progress<F>(&self, filter: F) -> Option<Column<Message>>
+    where
+        F: Fn(&JobKind) -> bool,
+    {
+        let job = self
+            .jobs
+            .values()
+            .rev()
+            .find(|job| job.status == JobStatus::Running && filter(&job.kind))?;
+        Some(
+            Column::new()
+                .push(Space::with_height(5))
+                .push(
+                    Row::new()
+                        .push(
+                            ProgressBar::new(0.0..=job.total.max(1) as f32, job.done as f32)
+                                .height(10),
+                        )
+                        .push(Space::with_width(10))
+                        .push(Self::button("Cancel", Some(Message::CancelJob(job.id))).width(80))
+                        .align_items(iced::alignment::Alignment::Center),
+                )
+                .push(Text::new(Self::job_details(job)).size(12)),
+        )
+    }
+
+    pub fn jobs_panel(&self) -> Container<Message> {
+        let seed = Row::new()
+            .push(Text::new(match self.seed {
+                Some(seed) => format!("Random seed: {}", seed),
+                None => "Random seed: -".to_string(),
+            }))
+            .push(Space::with_width(Length::Fill))
+            .push(
+                TextInput::new("seed[/job] (random if empty)", &self.seed_input)
+                    .on_input(Message::SeedInput)
+                    .width(200),
+            )
+            .push(Space::with_width(10))
+            .push(Self::button("Reseed", Some(Message::SetSeed)))
+            .align_items(iced::alignment::Alignment::Center);
+
+        let mut address_types = Row::new()
+            .push(Text::new(
+                "Address types of the next jobs & autoblock runs:",
+            ))
+            .align_items(iced::alignment::Alignment::Center);
+        for address_type in AddressType::ALL {
+            address_types = address_types.push(Space::with_width(15)).push(
+                Checkbox::new(
+                    address_type.to_string(),
+                    self.address_mix.0.contains(&address_type),
+                )
+                .on_toggle(move |checked| Message::ToggleAddressType(address_type, checked)),
+            );
+        }
+        let address_types = Column::new().push(address_types).push(Text::new(
+            "Picked at random for each address, none: P2PKH random addresses & node default \
+             wallet addresses (P2WSH is random addresses only)",
+        ));
+
+        let mut col = Column::new()
+            .push(seed)
+            .push(Space::with_height(5))
+            .push(address_types)
+            .push(Space::with_height(5))
+            .push(
+                Row::new()
+                    .push(Text::new(format!("Jobs ({} running)", self.running_jobs())))
+                    .push(Space::with_width(Length::Fill))
+                    .push(Self::button(
+                        "Clear finished",
+                        if self.jobs.len() > self.running_jobs() {
+                            Some(Message::ClearJobs)
+                        } else {
+                            None
+                        },
+                    ))
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+
+        for job in self.jobs.values().rev() {
+            let progress = if job.total > 0 {
+                Self::job_details(job)
+            } else {
+                "-".to_string()
+            };
+            let cancel = Self::button(
+                "Cancel",
+                if job.status == JobStatus::Running {
+                    Some(Message::CancelJob(job.id))
+                } else {
+                    None
+                },
+            )
+            .width(80);
+            col = col.push(Space::with_height(5)).push(
+                Row::new()
+                    .push(
+                        Text::new(format!(
+                            "#{} {} (seed {}, {} addresses)",
+                            job.id, job.label, job.seed, job.address_mix
+                        ))
+                        .width(Length::Fill),
+                    )
+                    .push(Space::with_width(10))
+                    .push(Text::new(progress))
+                    .push(Space::with_width(10))
+                    .push(Text::new(job.status.to_string()))
+                    .push(Space::with_width(10))
+                    .push(cancel)
+                    .align_items(iced::alignment::Alignment::Center),
+            );
+        }
+
+        Container::new(scrollable(col).height(Length::Fill))
+    }
+
+    pub fn console_panel(&self) -> Container<Message> {
+        let console = TextEditor::new(&self.console).on_action(Message::ConsoleEdit);
+
+        Container::new(scrollable(console).height(300))
+    }
+
+    pub fn u32_checked(input: String, output: &mut String, max: u32) {
+        if let Ok(blocks) = u32::from_str(&input) {
+            if blocks <= max {
+                *output = input;
+            }
+        } else if input.is_empty() {
+            *output = input;
+        }
+    }
+    pub fn amount_checked(input: String, output: &mut String) {
+        if Amount::from_str_in(&input, miniscript::bitcoin::Denomination::Bitcoin).is_ok()
+            || input.is_empty()
         {
             *output = input;
         }
@@ -1017,12 +3533,17 @@ impl Application for Gui {
     type Flags = Flags;
 
     fn new(flags: Self::Flags) -> (Self, Command<Message>) {
-        let gui = Gui {
+        let mut gui = Gui {
             receiver: flags.receiver,
             sender: flags.sender,
             config: Config::new(),
+            profile: None,
+            profile_name: String::new(),
             block_height: Some(0),
-            balance: Some(Amount::ZERO),
+            median_time: None,
+            mock_time: None,
+            balance: Some(Balances::default()),
+            bootstrap_amount: String::new(),
             generate_blocks: "".to_string(),
             generate_address: "".to_string(),
             generate_descriptor: "".to_string(),
@@ -1040,13 +3561,95 @@ impl Application for Gui {
             autoblock_wip: false,
             autoblock_blocks: "1".to_string(),
             autoblocks_timeframe: TimeFrame::Second,
-            generate_wip: false,
-            send_wip: false,
+            autoblock_model: IntervalModel::Fixed,
+            autoblock_replay_path: String::new(),
+            next_block: None,
+            autoblock_target: GenerateTarget::Random,
+            autoblock_target_input: String::new(),
+            autoblock_descriptor_index: "0".to_string(),
+            jobs: BTreeMap::new(),
+            health: NodeHealth::Healthy,
+            network: None,
+            signet_key: String::new(),
+            signet_key_set: false,
+            seed: None,
+            seed_input: String::new(),
+            address_mix: AddressMix::default(),
+            wallet_active: None,
+            wallets_loaded: Vec::new(),
+            wallets_available: Vec::new(),
+            new_wallet_name: String::new(),
+            new_wallet_descriptors: true,
+            new_wallet_private_keys: true,
+            new_wallet_passphrase: String::new(),
+            unlock_passphrase: String::new(),
+            tab: Tab::Main,
+            external_descriptor: String::new(),
+            external_loaded: false,
+            external_balance: None,
+            external_amount: String::new(),
+            external_address: String::new(),
+            external_back_min: String::new(),
+            external_back_max: String::new(),
+            external_back_blocks: String::new(),
+            external_back_enabled: false,
+            external_back_sweep: false,
+            external_wip: false,
+            psbt: String::new(),
+            psbt_others: String::new(),
+            psbt_summary: None,
+            psbt_wip: false,
+            raw_tx: String::new(),
+            raw_tx_mine: true,
+            raw_tx_summary: None,
+            raw_tx_wip: false,
+            mempool: Vec::new(),
+            block_selected: BTreeSet::new(),
+            block_raw_txs: String::new(),
+            block_target: GenerateTarget::Random,
+            block_address: String::new(),
+            block_wip: false,
+            censor_rules: Vec::new(),
+            censor_kind: CensorKind::FeeRate,
+            censor_input: String::new(),
+            censor_blocks: "6".to_string(),
+            schedules: Vec::new(),
+            schedule_target: GenerateTarget::RoundRobin,
+            schedule_input: String::new(),
+            schedule_index: "0".to_string(),
+            schedule_min: String::new(),
+            schedule_max: String::new(),
+            schedule_every: "1".to_string(),
+            schedule_unit: FrequencyUnit::Blocks,
+            actors: Vec::new(),
+            actor_name: String::new(),
+            actor_kind: ActorKind::Exchange,
+            actor_blocks: "6".to_string(),
+            actor_outputs: "10".to_string(),
+            actor_rate: "1".to_string(),
+            actor_utxos: "20".to_string(),
+            actor_min: String::new(),
+            actor_max: String::new(),
+            actor_funding: String::new(),
+            timelock_height: String::new(),
+            timelock_outpoint: String::new(),
+            timelock_confirmations: String::new(),
+            timelock_timestamp: String::new(),
+            mock_time_input: String::new(),
+            mock_time_jump: "1".to_string(),
+            mock_time_jump_unit: JumpUnit::Day,
+            mock_time_step: "600".to_string(),
+            mock_time_step_enabled: false,
             generate_target: GenerateTarget::Address,
             console: Content::new(),
             new_receive_address: None,
             reorg_blocks: String::new(),
         };
+        gui.seed_input = gui
+            .config
+            .seed
+            .map(|seed| seed.to_string())
+            .unwrap_or_default();
 
         (gui, Command::none())
     }
@@ -1061,31 +3664,79 @@ impl Application for Gui {
                 BitcoinMessage::UpdateBlockchainTip(block_height) => {
                     self.block_height = Some(block_height)
                 }
-                BitcoinMessage::UpdateBalance(amount) => self.balance = Some(amount),
-                BitcoinMessage::GenerateResponse(success) => {
-                    self.generate_wip = false;
-                    if !success {
-                        self.print("Fail to generate!")
-                    }
-                }
-                BitcoinMessage::SendResponse(success) => {
-                    self.send_wip = false;
-                    if !success {
-                        self.print("Fail to send!")
+                BitcoinMessage::UpdateBalance(balances) => self.balance = Some(balances),
+                BitcoinMessage::UpdateMedianTime(time) => self.median_time = Some(time),
+                BitcoinMessage::UpdateMockTime(time) => self.mock_time = time,
+                BitcoinMessage::JobUpdate(job) => {
+                    // failures are reported by the job itself w/ a hint
+                    if matches!(job.status, JobStatus::Done | JobStatus::Cancelled) {
+                        self.print(&format!("Job #{} ({}) {}", job.id, job.label, job.status));
                     }
+                    self.jobs.insert(job.id, job);
                 }
                 BitcoinMessage::SendMessage(msg) => {
                     self.print(&msg);
                 }
+                BitcoinMessage::UpdateHealth(health) => self.health = health,
+                BitcoinMessage::UpdateNetwork(network) => {
+                    self.network = Some(network);
+                    if !self.can_mine() {
+                        self.raw_tx_mine = false;
+                        self.print(&format!(
+                            "Connected to {}, block generation disabled",
+                            network
+                        ));
+                    }
+                }
+                BitcoinMessage::Seed(seed) => self.seed = Some(seed),
+                BitcoinMessage::SignetKeySet(set) => {
+                    self.signet_key_set = set;
+                    if set {
+                        self.signet_key.clear();
+                    }
+                }
+                BitcoinMessage::Wallets {
+                    active,
+                    loaded,
+                    available,
+                } => {
+                    self.wallet_active = Some(active);
+                    self.wallets_loaded = loaded;
+                    self.wallets_available = available;
+                }
                 BitcoinMessage::Connected(connected) => {
                     self.connected = connected;
+                    self.health = NodeHealth::Healthy;
                     if connected {
                         if let Err(e) = self.config.save() {
                             self.print(&e);
                         }
+                    } else {
+                        self.network = None;
+                        self.signet_key_set = false;
+                        self.mempool.clear();
+                        self.schedules.clear();
+                        self.actors.clear();
+                        self.block_selected.clear();
+                        self.block_wip = false;
+                        self.wallet_active = None;
+                        self.wallets_loaded.clear();
+                        self.wallets_available.clear();
+                        self.external_loaded = false;
+                        self.external_back_enabled = false;
+                        self.mock_time = None;
+                        self.mock_time_step_enabled = false;
                     }
                 }
-                BitcoinMessage::MinerStopped => self.autoblock_wip = false,
+                BitcoinMessage::MinerStopped => {
+                    self.autoblock_wip = false;
+                    self.next_block = None;
+                }
+                BitcoinMessage::NextBlockIn(delay) => {
+                    let at = chrono::Local::now()
+                        + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::zero());
+                    self.next_block = Some((at, delay));
+                }
                 BitcoinMessage::IncrementSendDescriptorIndex => {
                     if let Ok(index) = u32::from_str(&self.send_descriptor_index) {
                         let index = index.wrapping_add(1);
@@ -1098,7 +3749,62 @@ impl Application for Gui {
                         self.generate_descriptor_index = index.to_string();
                     }
                 }
+                BitcoinMessage::IncrementAutoblockDescriptorIndex => {
+                    if let Ok(index) = u32::from_str(&self.autoblock_descriptor_index) {
+                        let index = index.wrapping_add(1);
+                        self.autoblock_descriptor_index = index.to_string();
+                    }
+                }
                 BitcoinMessage::NewAddress(addr) => self.new_receive_address = Some(addr),
+                BitcoinMessage::ExternalWalletLoaded(loaded) => {
+                    self.external_wip = false;
+                    self.external_loaded = loaded;
+                }
+                BitcoinMessage::UpdateExternalBalance(amount) => {
+                    self.external_balance = Some(amount)
+                }
+                BitcoinMessage::PsbtDecoded(summary) => self.psbt_summary = Some(summary),
+                BitcoinMessage::PsbtUpdated(psbt) => {
+                    self.psbt = psbt;
+                    self.psbt_others.clear();
+                    self.psbt_summary = None;
+                }
+                BitcoinMessage::PsbtResponse(success) => {
+                    self.psbt_wip = false;
+                    if !success {
+                        self.print("PSBT operation failed!")
+                    }
+                }
+                BitcoinMessage::RawTxDecoded(summary) => self.raw_tx_summary = Some(summary),
+                BitcoinMessage::RawTxResponse(success) => {
+                    self.raw_tx_wip = false;
+                    if !success {
+                        self.print("Raw transaction operation failed!")
+                    }
+                }
+                BitcoinMessage::Mempool(mempool) => {
+                    // drop the selected txs that left the mempool
+                    self.block_selected
+                        .retain(|txid| mempool.iter().any(|tx| tx.txid == *txid));
+                    self.mempool = mempool;
+                }
+                BitcoinMessage::CensorRules(rules) => self.censor_rules = rules,
+                BitcoinMessage::Schedules(schedules) => self.schedules = schedules,
+                BitcoinMessage::Actors(actors) => self.actors = actors,
+                BitcoinMessage::BlockBuilderResponse(success) => {
+                    self.block_wip = false;
+                    if success {
+                        self.block_raw_txs.clear();
+                    } else {
+                        self.print("Fail to build block!")
+                    }
+                }
+                BitcoinMessage::ExternalSendResponse(success) => {
+                    self.external_wip = false;
+                    if !success {
+                        self.print("Fail to send from external wallet!")
+                    }
+                }
                 _ => {}
             },
 
@@ -1107,6 +3813,55 @@ impl Application for Gui {
             Message::User(user) => self.config.bitcoind.user = user,
             Message::Password(pass) => self.config.bitcoind.password = pass,
             Message::CookiePath(path) => self.config.bitcoind.cookie_path = path,
+            Message::WalletName(name) => self.config.bitcoind.wallet = name,
+            Message::PasswordSource(source) => self.config.bitcoind.password_source = source,
+            Message::PasswordRef(reference) => self.config.bitcoind.password_ref = reference,
+            Message::Profile(name) => self.load_profile(name),
+            Message::ProfileName(name) => self.profile_name = name,
+            Message::SaveProfile => self.save_profile(),
+            Message::NewWalletName(name) => self.new_wallet_name = name,
+            Message::NewWalletPassphrase(pass) => self.new_wallet_passphrase = pass,
+            Message::ToggleNewWalletDescriptors(descriptors) => {
+                self.new_wallet_descriptors = descriptors
+            }
+            Message::ToggleNewWalletPrivateKeys(private_keys) => {
+                self.new_wallet_private_keys = private_keys
+            }
+            Message::UnlockPassphrase(pass) => self.unlock_passphrase = pass,
+            Message::SignetKey(key) => self.signet_key = key,
+            Message::SeedInput(seed) => {
+                // a trailing `/` while typing the job
+                if seed.is_empty()
+                    || JobSeed::from_str(seed.strip_suffix('/').unwrap_or(&seed)).is_ok()
+                {
+                    self.seed_input = seed;
+                }
+            }
+            Message::SetSeed => self.set_seed(),
+            Message::ToggleAddressType(address_type, checked) => {
+                if checked {
+                    self.address_mix.0.insert(address_type);
+                } else {
+                    self.address_mix.0.remove(&address_type);
+                }
+                self.send_to_bitcoind(BitcoinMessage::SetAddressMix(self.address_mix.clone()));
+            }
+            Message::SetSignetKey => {
+                self.send_to_bitcoind(BitcoinMessage::SetSignetKey(self.signet_key.clone()))
+            }
+            Message::SelectWallet(name) => {
+                self.send_to_bitcoind(BitcoinMessage::SelectWallet(name))
+            }
+            Message::LoadWallet(name) => self.send_to_bitcoind(BitcoinMessage::LoadWallet(name)),
+            Message::UnloadWallet(name) => {
+                self.send_to_bitcoind(BitcoinMessage::UnloadWallet(name))
+            }
+            Message::CreateWallet => self.create_wallet(),
+            Message::UnlockWallet => {
+                let passphrase = std::mem::take(&mut self.unlock_passphrase);
+                self.send_to_bitcoind(BitcoinMessage::UnlockWallet(passphrase))
+            }
+            Message::RefreshWallets => self.send_to_bitcoind(BitcoinMessage::ListWallets),
             Message::BlocksGenerate(blocks) => {
                 Self::u32_checked(blocks, &mut self.generate_blocks, 10_000)
             }
@@ -1131,11 +3886,102 @@ impl Application for Gui {
                 Self::u32_checked(blocks, &mut self.autoblock_blocks, 1_000)
             }
             Message::AutoblockTimeframe(tf) => self.autoblocks_timeframe = tf,
+            Message::AutoblockModel(model) => self.autoblock_model = model,
+            Message::AutoblockReplayPath(path) => self.autoblock_replay_path = path,
+            Message::AutoblockTarget(target) => self.autoblock_target = target,
+            Message::AutoblockTargetInput(input) => self.autoblock_target_input = input,
+            Message::AutoblockDescriptorIndex(index) => {
+                Self::u32_checked(index, &mut self.autoblock_descriptor_index, MAX_DERIV)
+            }
             Message::InvalidateBlocks(blocks) => {
                 if u64::from_str(&blocks).is_ok() || blocks.is_empty() {
                     self.reorg_blocks = blocks;
                 }
             }
+            Message::Tab(tab) => {
+                if tab == Tab::BlockBuilder && self.connected {
+                    self.send_to_bitcoind(BitcoinMessage::ListMempool);
+                }
+                self.tab = tab;
+            }
+            Message::Psbt(psbt) => {
+                self.psbt = psbt.trim().to_string();
+                self.psbt_summary = None;
+            }
+            Message::PsbtOthers(psbts) => self.psbt_others = psbts,
+            Message::TimelockHeight(height) => {
+                if u64::from_str(&height).is_ok() || height.is_empty() {
+                    self.timelock_height = height;
+                }
+            }
+            Message::TimelockOutpoint(outpoint) => self.timelock_outpoint = outpoint,
+            Message::TimelockConfirmations(confs) => {
+                Self::u32_checked(confs, &mut self.timelock_confirmations, 10_000)
+            }
+            Message::TimelockTimestamp(timestamp) => self.timelock_timestamp = timestamp,
+            Message::MockTime(timestamp) => self.mock_time_input = timestamp,
+            Message::MockTimeJump(count) => {
+                if u64::from_str(&count).is_ok() || count.is_empty() {
+                    self.mock_time_jump = count;
+                }
+            }
+            Message::MockTimeJumpUnit(unit) => self.mock_time_jump_unit = unit,
+            Message::MockTimeStep(step) => {
+                if u64::from_str(&step).is_ok() || step.is_empty() {
+                    self.mock_time_step = step;
+                }
+            }
+            Message::BlockRawTxs(hex) => self.block_raw_txs = hex,
+            Message::BlockTarget(target) => self.block_target = target,
+            Message::BlockAddress(address) => self.block_address = address,
+            Message::BootstrapAmount(amount) => {
+                Self::amount_checked(amount, &mut self.bootstrap_amount)
+            }
+            Message::ScheduleTarget(target) => self.schedule_target = target,
+            Message::ScheduleInput(input) => self.schedule_input = input,
+            Message::ScheduleIndex(index) => {
+                Self::u32_checked(index, &mut self.schedule_index, u32::MAX)
+            }
+            Message::ScheduleMin(min) => Self::amount_checked(min, &mut self.schedule_min),
+            Message::ScheduleMax(max) => Self::amount_checked(max, &mut self.schedule_max),
+            Message::ScheduleEvery(every) => {
+                Self::u32_checked(every, &mut self.schedule_every, 1_000_000)
+            }
+            Message::ScheduleUnit(unit) => self.schedule_unit = unit,
+            Message::ActorName(name) => self.actor_name = name,
+            Message::ActorKind(kind) => self.actor_kind = kind,
+            Message::ActorBlocks(blocks) => {
+                Self::u32_checked(blocks, &mut self.actor_blocks, 1_000_000)
+            }
+            Message::ActorOutputs(outputs) => {
+                Self::u32_checked(outputs, &mut self.actor_outputs, 1_000)
+            }
+            Message::ActorRate(rate) => self.actor_rate = rate,
+            Message::ActorUtxos(utxos) => Self::u32_checked(utxos, &mut self.actor_utxos, 10_000),
+            Message::ActorMin(min) => Self::amount_checked(min, &mut self.actor_min),
+            Message::ActorMax(max) => Self::amount_checked(max, &mut self.actor_max),
+            Message::ActorFunding(funding) => {
+                Self::amount_checked(funding, &mut self.actor_funding)
+            }
+            Message::CensorKind(kind) => self.censor_kind = kind,
+            Message::CensorInput(input) => self.censor_input = input,
+            Message::CensorBlocks(blocks) => {
+                Self::u32_checked(blocks, &mut self.censor_blocks, 10_000)
+            }
+            Message::RawTx(hex) => {
+                self.raw_tx = hex.trim().to_string();
+                self.raw_tx_summary = None;
+            }
+            Message::ExternalDescriptor(descriptor) => self.external_descriptor = descriptor,
+            Message::ExternalAmount(amount) => {
+                Self::amount_checked(amount, &mut self.external_amount)
+            }
+            Message::ExternalAddress(address) => self.external_address = address,
+            Message::ExternalBackMin(min) => Self::amount_checked(min, &mut self.external_back_min),
+            Message::ExternalBackMax(max) => Self::amount_checked(max, &mut self.external_back_max),
+            Message::ExternalBackBlocks(blocks) => {
+                Self::u32_checked(blocks, &mut self.external_back_blocks, 10_000)
+            }
 
             // Buttons
             Message::ConnectRpcAuth => self.connect_rpc_auth(),
@@ -1147,6 +3993,7 @@ impl Application for Gui {
             Message::StopAutoblock => self.stop_auto_block(),
             Message::GenerateToAddress => self.generate_to_address(),
             Message::GenerateToSelf => self.generate_to_self(),
+            Message::Bootstrap => self.bootstrap(),
             Message::GenerateToRandom => self.generate_to_random(),
             Message::GenerateToDescriptor => self.generate_to_descriptor(),
             Message::SendToAddress => self.send_to_address(),
@@ -1168,6 +4015,75 @@ impl Application for Gui {
             Message::ToggleEveryBlock(enable) => {
                 self.toggle_every_blocks(enable);
             }
+            Message::LoadExternal => self.load_external(),
+            Message::ExternalSendToAddress => self.external_send_to_address(),
+            Message::ExternalSendToSelf => self.external_send_to_self(),
+            Message::ExternalSweep => {
+                self.external_wip = true;
+                self.send_to_bitcoind(BitcoinMessage::ExternalSweep);
+            }
+            Message::ToggleSendBack(enable) => self.toggle_send_back(enable),
+            Message::ToggleSendBackSweep(sweep) => self.external_back_sweep = sweep,
+            Message::DecodePsbt => self.psbt_action(BitcoinMessage::DecodePsbt(self.psbt.clone())),
+            Message::SignPsbt => self.psbt_action(BitcoinMessage::SignPsbt(self.psbt.clone())),
+            Message::CombinePsbt => self.combine_psbt(),
+            Message::FinalizePsbt => self.psbt_action(BitcoinMessage::FinalizePsbt {
+                psbt: self.psbt.clone(),
+                broadcast: false,
+            }),
+            Message::BroadcastPsbt => self.psbt_action(BitcoinMessage::FinalizePsbt {
+                psbt: self.psbt.clone(),
+                broadcast: true,
+            }),
+            Message::DecodeRawTx => {
+                self.raw_tx_action(BitcoinMessage::DecodeRawTx(self.raw_tx.clone()))
+            }
+            Message::TestRawTx => {
+                self.raw_tx_action(BitcoinMessage::TestRawTx(self.raw_tx.clone()))
+            }
+            Message::BroadcastRawTx => self.raw_tx_action(BitcoinMessage::BroadcastRawTx {
+                hex: self.raw_tx.clone(),
+                mine: self.raw_tx_mine,
+            }),
+            Message::ToggleRawTxMine(mine) => self.raw_tx_mine = mine,
+            Message::RefreshMempool => self.send_to_bitcoind(BitcoinMessage::ListMempool),
+            Message::ToggleBlockTx(txid, selected) => {
+                if selected {
+                    self.block_selected.insert(txid);
+                } else {
+                    self.block_selected.remove(&txid);
+                }
+            }
+            Message::SelectAllMempool(selected) => {
+                self.block_selected = if selected {
+                    self.mempool.iter().map(|tx| tx.txid).collect()
+                } else {
+                    BTreeSet::new()
+                }
+            }
+            Message::BuildBlock => self.build_block(),
+            Message::AddSchedule => self.add_schedule(),
+            Message::AddActor => self.add_actor(),
+            Message::RemoveActor(id) => self.send_to_bitcoind(BitcoinMessage::RemoveActor(id)),
+            Message::RemoveSchedule(id) => {
+                self.send_to_bitcoind(BitcoinMessage::RemoveSchedule(id))
+            }
+            Message::AddCensorRule => self.add_censor_rule(),
+            Message::RemoveCensorRule(index) => {
+                if index < self.censor_rules.len() {
+                    self.censor_rules.remove(index);
+                    self.update_censor_rules();
+                }
+            }
+            Message::MineUntilHeight => self.mine_until_height(),
+            Message::MineUntilConfirmed => self.mine_until_confirmed(),
+            Message::AdvanceMtp => self.advance_mtp(),
+            Message::SetMockTime => self.set_mock_time(),
+            Message::ResetMockTime => self.send_to_bitcoind(BitcoinMessage::SetMockTime(0)),
+            Message::JumpMockTime => self.jump_mock_time(),
+            Message::ToggleMockTimeStep(enable) => self.toggle_mock_time_step(enable),
+            Message::CancelJob(id) => self.send_to_bitcoind(BitcoinMessage::CancelJob(id)),
+            Message::ClearJobs => self.jobs.retain(|_, job| job.status == JobStatus::Running),
             Message::KeyPressed(Key::Tab(shift)) => {
                 if shift {
                     return focus_previous();
@@ -1197,23 +4113,27 @@ impl Application for Gui {
             .push(Space::with_height(5))
             .push(Rule::horizontal(4))
             .push(Space::with_height(5))
-            .push(self.auto_block_panel())
+            .push(self.tab_panel())
             .push(Space::with_height(5))
             .push(Rule::horizontal(4))
-            .push(Space::with_height(5))
-            .push(self.generate_panel())
-            .push(Space::with_height(5))
-            .push(Rule::horizontal(4))
-            .push(Space::with_height(5))
-            .push(self.invalidate_panel())
-            .push(Space::with_height(5))
-            .push(Rule::horizontal(4))
-            .push(Space::with_height(5))
-            .push(self.address_panel())
-            .push(Space::with_height(5))
-            .push(Rule::horizontal(4))
-            .push(Space::with_height(5))
-            .push(self.send_panel())
+            .push(Space::with_height(5));
+
+        let main_frame = match self.tab {
+            Tab::Main => main_frame.push(self.main_tab()),
+            Tab::External => main_frame.push(self.external_panel()),
+            Tab::Psbt => main_frame.push(self.psbt_panel()),
+            Tab::RawTx => main_frame.push(self.raw_tx_panel()),
+            Tab::BlockBuilder => main_frame.push(self.block_builder_panel()),
+            Tab::Censorship => main_frame.push(self.censorship_panel()),
+            Tab::Scheduler => main_frame.push(self.scheduler_panel()),
+            Tab::Actors => main_frame.push(self.actors_panel()),
+            Tab::Timelock => main_frame.push(self.timelock_panel()),
+            Tab::Time => main_frame.push(self.time_panel()),
+            Tab::Jobs => main_frame.push(self.jobs_panel()),
+            Tab::Wallets => main_frame.push(self.wallets_panel()),
+        };
+
+        let main_frame = main_frame
             .push(Space::with_height(5))
             .push(self.console_panel())
             .push(Space::with_height(5))
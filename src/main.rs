@@ -1,6 +1,15 @@
+mod actor;
 mod bitcoind;
+mod descriptor;
+mod error;
 mod gui;
+mod miner;
+mod psbt;
+mod rawtx;
+mod scheduler;
 mod service;
+mod signet;
+mod traffic;
 
 use crate::gui::Flags;
 use bitcoind::{BitcoinD, BitcoinMessage};
@@ -53,7 +62,7 @@ async fn main() {
         .apply()
         .unwrap();
 
-    let (gui_sender, bitcoin_receiver) = std::sync::mpsc::channel::<BitcoinMessage>();
+    let (gui_sender, bitcoin_receiver) = async_channel::unbounded::<BitcoinMessage>();
     let (bitcoin_sender, gui_receiver) = async_channel::unbounded::<BitcoinMessage>();
 
     let bitcoind = BitcoinD::new(bitcoin_sender, bitcoin_receiver, gui_sender.clone());
@@ -65,7 +74,7 @@ async fn main() {
 
     settings.window.size = Size {
         width: 500.0,
-        height: 700.0,
+        height: 750.0,
     };
     settings.window.resizable = false;
 
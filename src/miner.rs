@@ -0,0 +1,645 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    fs,
+    str::FromStr,
+    time::Duration,
+};
+
+use bitcoincore_rpc::{Client, RpcApi};
+use miniscript::{
+    bitcoin::{
+        secp256k1::{All, Secp256k1},
+        Address, Amount, Network, PrivateKey, ScriptBuf, Txid,
+    },
+    Descriptor, DescriptorPublicKey,
+};
+use rand::{rngs::StdRng, Rng};
+use tokio::time::Instant;
+
+use crate::{
+    bitcoind::{AutoBlockMessage, MempoolTx},
+    descriptor::parse_descriptor,
+    error::Error,
+    signet,
+};
+
+/// Model of the delay between two blocks mined by the autoblock miner.
+#[derive(Debug, Clone)]
+pub enum BlockInterval {
+    /// Mine a block at a fixed interval
+    Fixed(Duration),
+    /// Exponentially distributed intervals (like a real chain) w/ the given mean
+    Exponential(Duration),
+    /// Replay a list of intervals, looping over it
+    Replay(Vec<Duration>),
+}
+
+impl BlockInterval {
+    /// Load a replay list from a file containing one interval (in seconds) per line,
+    /// empty lines and lines starting with `#` are ignored.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Fail to read {}: {}", path, e))?;
+        let intervals = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                f64::from_str(line)
+                    .ok()
+                    .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+                    .ok_or_else(|| format!("Invalid interval: {}", line))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if intervals.is_empty() {
+            return Err(format!("No interval found in {}", path));
+        }
+        Ok(BlockInterval::Replay(intervals))
+    }
+
+    /// Returns the delay to wait before mining the `index`-th block.
+    pub fn delay<R: Rng>(&self, rng: &mut R, index: usize) -> Duration {
+        match self {
+            BlockInterval::Fixed(delay) => *delay,
+            BlockInterval::Exponential(mean) => {
+                // inverse transform sampling, 1 - u is in (0, 1]
+                let u: f64 = rng.gen();
+                mean.mul_f64(-(1.0 - u).ln())
+            }
+            BlockInterval::Replay(intervals) => intervals[index % intervals.len()],
+        }
+    }
+}
+
+/// Schedule of the autoblock miner, the delay to the next block is drawn once per block
+/// from its own generator: control messages and pauses neither redraw nor shift it.
+#[derive(Debug)]
+pub struct BlockClock {
+    interval: BlockInterval,
+    rng: StdRng,
+    index: usize,
+    delay: Duration,
+    /// `None` while paused
+    deadline: Option<Instant>,
+}
+
+impl BlockClock {
+    pub fn new(interval: BlockInterval, mut rng: StdRng, now: Instant) -> Self {
+        let delay = interval.delay(&mut rng, 0);
+        BlockClock {
+            interval,
+            rng,
+            index: 0,
+            delay,
+            deadline: Some(now + delay),
+        }
+    }
+
+    /// When to mine the next block, `None` while paused.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Time left before the next block, `None` while paused.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// A block was mined (or attempted) at `now`, draw the delay to the next one.
+    pub fn mined(&mut self, now: Instant) {
+        self.index += 1;
+        self.delay = self.interval.delay(&mut self.rng, self.index);
+        self.deadline = Some(now + self.delay);
+    }
+
+    /// Apply the timing part of a miner message: a pause stops the clock, a resume waits
+    /// the pending delay again from `now`, the others leave the deadline untouched.
+    pub fn on_message(&mut self, msg: &AutoBlockMessage, now: Instant) {
+        match msg {
+            AutoBlockMessage::Pause => self.deadline = None,
+            AutoBlockMessage::Resume { .. } => {
+                if self.deadline.is_none() {
+                    self.deadline = Some(now + self.delay);
+                }
+            }
+            AutoBlockMessage::Stop
+            | AutoBlockMessage::UpdateClients { .. }
+            | AutoBlockMessage::SetCensorRules(_) => {}
+        }
+    }
+}
+
+/// Destination of the coinbase outputs of the blocks mined by the autoblock miner.
+#[derive(Debug, Clone)]
+pub enum PayoutTarget {
+    /// A new random address (burned coins) for every block
+    Random,
+    /// A new address of rpcwallet 'regtest' for every block
+    ToSelf,
+    /// Always the same address
+    Address(Address),
+    /// Derive a new address from the descriptor for every block
+    Descriptor {
+        descriptor: Descriptor<DescriptorPublicKey>,
+        start_index: u32,
+    },
+    /// Loop over a list of addresses
+    RoundRobin(Vec<Address>),
+}
+
+/// Confirmations a coinbase output needs to be spendable (counting its own block
+/// as the first one, it's spendable at depth `COINBASE_MATURITY + 1`).
+pub const COINBASE_MATURITY: u64 = 100;
+/// Max blocks mined by a wallet bootstrap job.
+pub const MAX_BOOTSTRAP_BLOCKS: u32 = 1000;
+
+/// Block subsidy at `height`, regtest halves every 150 blocks.
+pub fn block_subsidy(height: u64, network: Network) -> Amount {
+    let interval = match network {
+        Network::Regtest => 150,
+        _ => 210_000,
+    };
+    let halvings = height / interval;
+    if halvings >= 64 {
+        Amount::ZERO
+    } else {
+        Amount::from_sat((50 * 100_000_000) >> halvings)
+    }
+}
+
+/// Count of blocks to mine to self on top of `height` so the new coinbases, once mature,
+/// add up to `missing`, fees are ignored. `None` if not reachable in `MAX_BOOTSTRAP_BLOCKS`.
+pub fn bootstrap_blocks(height: u64, network: Network, missing: Amount) -> Option<u32> {
+    let mut matured = Amount::ZERO;
+    for n in 1..=MAX_BOOTSTRAP_BLOCKS {
+        // once the tip is at `height + n`, the coinbase at `height + n - 100` is mature
+        if let Some(k) = (n as u64).checked_sub(COINBASE_MATURITY).filter(|k| *k > 0) {
+            matured += block_subsidy(height + k, network);
+        }
+        if matured >= missing {
+            return Some(n);
+        }
+    }
+    None
+}
+
+/// How blocks are produced.
+#[derive(Debug, Clone)]
+pub enum MiningBackend {
+    /// `generatetoaddress`, regtest only
+    GenerateToAddress,
+    /// Build the block from `getblocktemplate`, sign it w/ the signet challenge key
+    /// and `submitblock` it
+    Signet(PrivateKey),
+}
+
+impl MiningBackend {
+    /// Mine one block paying to `address`.
+    pub fn mine(
+        &self,
+        client: &Client,
+        secp: &Secp256k1<All>,
+        address: &Address,
+    ) -> Result<(), Error> {
+        match self {
+            MiningBackend::GenerateToAddress => client
+                .generate_to_address(1, address)
+                .map(|_| ())
+                .map_err(Error::from),
+            MiningBackend::Signet(key) => {
+                signet::mine_block(client, secp, key, address).map(|_| ())
+            }
+        }
+    }
+}
+
+/// Count of indexes derived to match the outputs of a descriptor censorship rule.
+pub const CENSOR_DESCRIPTOR_RANGE: u32 = 1000;
+
+/// Which mempool transactions a censorship rule excludes.
+#[derive(Debug, Clone)]
+pub enum CensorCriteria {
+    /// Transactions paying to one of the first `CENSOR_DESCRIPTOR_RANGE` addresses
+    /// of the descriptor
+    Descriptor {
+        descriptor: String,
+        /// Derived by the service (`derive()`), empty until then
+        scripts: BTreeSet<ScriptBuf>,
+    },
+    /// Transactions w/ a fee rate (sat/vB) below this one
+    FeeRateBelow(f64),
+    Txids(BTreeSet<Txid>),
+}
+
+impl CensorCriteria {
+    /// Parse (only) `descriptor`, its scripts are derived by `derive()`.
+    pub fn descriptor(descriptor: &str) -> Result<Self, Error> {
+        parse_descriptor(descriptor.trim())?;
+        Ok(CensorCriteria::Descriptor {
+            descriptor: descriptor.trim().to_string(),
+            scripts: BTreeSet::new(),
+        })
+    }
+
+    /// Derive the scripts of a descriptor criteria if not done yet, no-op for the others.
+    pub fn derive(&mut self, secp: &Secp256k1<All>) -> Result<(), Error> {
+        let CensorCriteria::Descriptor {
+            descriptor,
+            scripts,
+        } = self
+        else {
+            return Ok(());
+        };
+        if !scripts.is_empty() {
+            return Ok(());
+        }
+        let parsed = parse_descriptor(descriptor)?;
+        for single in parsed
+            .into_single_descriptors()
+            .map_err(|e| Error::ParseDescriptor {
+                message: e.to_string(),
+                position: None,
+            })?
+        {
+            let range = if single.has_wildcard() {
+                CENSOR_DESCRIPTOR_RANGE
+            } else {
+                1
+            };
+            for index in 0..range {
+                let derived = single
+                    .derived_descriptor(secp, index)
+                    .map_err(|_| Error::DeriveDescriptor)?;
+                scripts.insert(derived.script_pubkey());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if a transaction w/ this `fee_rate` and these outputs `scripts`
+    /// is excluded.
+    pub fn matches(&self, txid: &Txid, fee_rate: f64, scripts: &[ScriptBuf]) -> bool {
+        match self {
+            CensorCriteria::Descriptor {
+                scripts: censored, ..
+            } => scripts.iter().any(|script| censored.contains(script)),
+            CensorCriteria::FeeRateBelow(min) => fee_rate < *min,
+            CensorCriteria::Txids(txids) => txids.contains(txid),
+        }
+    }
+}
+
+/// Exclude the transactions matching `criteria` (and their descendants) from the next
+/// `blocks` blocks mined by the autoblock miner.
+#[derive(Debug, Clone)]
+pub struct CensorRule {
+    pub criteria: CensorCriteria,
+    pub blocks: u32,
+}
+
+impl Display for CensorRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.criteria {
+            CensorCriteria::Descriptor { descriptor, .. } => write!(f, "paying to {}", descriptor)?,
+            CensorCriteria::FeeRateBelow(rate) => write!(f, "fee rate below {} sat/vB", rate)?,
+            CensorCriteria::Txids(txids) => write!(
+                f,
+                "txid in {}",
+                txids
+                    .iter()
+                    .map(|txid| txid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?,
+        }
+        write!(f, " for the next {} block(s)", self.blocks)
+    }
+}
+
+/// Max weight of the transactions of a block, the node block assembler reserves 4000 WU
+/// for the header & the coinbase.
+pub const MAX_BLOCK_TX_WEIGHT: u64 = 4_000_000 - 4_000;
+
+/// Select the transactions of a censored block from `mempool` (parents first, each
+/// transaction w/ its output scripts, only needed by descriptor rules), returns the
+/// included txids and the count of censored transactions.
+///
+/// The transactions matching a rule are censored and so are their descendants, the ones
+/// not fitting in `MAX_BLOCK_TX_WEIGHT` are left out (w/ their descendants) but not
+/// counted. The weight is bounded by the (sigop adjusted) mempool vsize.
+pub fn censored_block(
+    mempool: &[(MempoolTx, Vec<ScriptBuf>)],
+    rules: &[CensorRule],
+) -> (Vec<Txid>, usize) {
+    let mut censored = BTreeSet::new();
+    let mut left_out = BTreeSet::new();
+    let mut included = Vec::new();
+    let mut weight = 0;
+    for (tx, scripts) in mempool {
+        if tx.depends.iter().any(|parent| censored.contains(parent))
+            || rules
+                .iter()
+                .any(|rule| rule.criteria.matches(&tx.txid, tx.fee_rate(), scripts))
+        {
+            censored.insert(tx.txid);
+        } else if tx.depends.iter().any(|parent| left_out.contains(parent))
+            || weight + tx.vsize * 4 > MAX_BLOCK_TX_WEIGHT
+        {
+            left_out.insert(tx.txid);
+        } else {
+            weight += tx.vsize * 4;
+            included.push(tx.txid);
+        }
+    }
+    (included, censored.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoincore_rpc::Auth;
+    use miniscript::bitcoin::hashes::Hash;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn txid(byte: u8) -> Txid {
+        Txid::from_byte_array([byte; 32])
+    }
+
+    fn script(byte: u8) -> ScriptBuf {
+        ScriptBuf::from_bytes(vec![byte])
+    }
+
+    /// Mempool transaction `byte` of `vsize` vB paying 1 sat/vB, spending `depends`.
+    fn tx(byte: u8, vsize: u64, depends: &[u8]) -> (MempoolTx, Vec<ScriptBuf>) {
+        let tx = MempoolTx {
+            txid: txid(byte),
+            vsize,
+            fee: Amount::from_sat(vsize),
+            ancestors: depends.len() as u64 + 1,
+            time: 0,
+            depends: depends.iter().map(|parent| txid(*parent)).collect(),
+        };
+        (tx, vec![script(byte)])
+    }
+
+    fn rule(criteria: CensorCriteria) -> CensorRule {
+        CensorRule {
+            criteria,
+            blocks: 1,
+        }
+    }
+
+    #[test]
+    fn exponential_interval() {
+        const SAMPLES: usize = 100_000;
+        let mean = Duration::from_secs(600);
+        let interval = BlockInterval::Exponential(mean);
+        let mut rng = StdRng::seed_from_u64(42);
+        let samples = (0..SAMPLES)
+            .map(|index| interval.delay(&mut rng, index).as_secs_f64())
+            .collect::<Vec<_>>();
+        assert!(samples.iter().all(|delay| *delay >= 0.0));
+        let sample_mean = samples.iter().sum::<f64>() / SAMPLES as f64;
+        // an exponential's std deviation is its mean, so the mean of 100k delays is
+        // 600 +/- 1.9s, 10s only fails w/ a biased draw
+        assert!(
+            (sample_mean - 600.0).abs() <= 10.0,
+            "mean {} expected 600",
+            sample_mean
+        );
+        // P(delay > mean) = 1/e
+        let above = samples.iter().filter(|delay| **delay > 600.0).count() as f64;
+        assert!((above / SAMPLES as f64 - (-1.0f64).exp()).abs() < 0.01);
+
+        // the same seed gives the same intervals
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        for index in 0..1_000 {
+            assert_eq!(interval.delay(&mut a, index), interval.delay(&mut b, index));
+        }
+    }
+
+    #[test]
+    fn replay_interval() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let secs = Duration::from_secs;
+        let interval = BlockInterval::Replay(vec![secs(1), secs(2), secs(3)]);
+        let delays = (0..7)
+            .map(|index| interval.delay(&mut rng, index))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            vec![
+                secs(1),
+                secs(2),
+                secs(3),
+                secs(1),
+                secs(2),
+                secs(3),
+                secs(1)
+            ]
+        );
+        // the generator is not drawn
+        let mut reference = rng.clone();
+        interval.delay(&mut rng, 0);
+        assert_eq!(rng.gen::<u64>(), reference.gen::<u64>());
+
+        let fixed = BlockInterval::Fixed(secs(5));
+        assert_eq!(fixed.delay(&mut rng, 0), secs(5));
+        assert_eq!(fixed.delay(&mut rng, 100), secs(5));
+    }
+
+    #[test]
+    fn replay_file() {
+        let path = std::env::temp_dir().join(format!("minta_replay_{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "# intervals\n600\n\n  1.5 \n# end\n0\n").unwrap();
+        match BlockInterval::from_file(path) {
+            Ok(BlockInterval::Replay(intervals)) => assert_eq!(
+                intervals,
+                vec![
+                    Duration::from_secs(600),
+                    Duration::from_millis(1_500),
+                    Duration::ZERO
+                ]
+            ),
+            other => panic!("{:?}", other),
+        }
+
+        fs::write(path, "600\nten\n").unwrap();
+        let error = BlockInterval::from_file(path).unwrap_err();
+        assert!(error.contains("ten"), "{}", error);
+        fs::write(path, "-1\n").unwrap();
+        assert!(BlockInterval::from_file(path).is_err());
+        fs::write(path, "# no interval\n\n").unwrap();
+        assert!(BlockInterval::from_file(path).is_err());
+        fs::remove_file(path).unwrap();
+        assert!(BlockInterval::from_file(path).is_err());
+    }
+
+    #[test]
+    fn block_clock() {
+        let secs = Duration::from_secs;
+        let client = || Client::new("http://127.0.0.1:18443", Auth::None).unwrap();
+        let start = Instant::now();
+        let interval = BlockInterval::Exponential(secs(600));
+        let rng = StdRng::seed_from_u64(42);
+        let mut clock = BlockClock::new(interval.clone(), rng.clone(), start);
+        let mut reference = BlockClock::new(interval, rng, start);
+        let deadline = clock.deadline().unwrap();
+        let delay = deadline - start;
+
+        // control messages while waiting neither redraw nor shift the deadline
+        let now = start + secs(1);
+        clock.on_message(&AutoBlockMessage::SetCensorRules(vec![]), now);
+        let update = AutoBlockMessage::UpdateClients {
+            client: client(),
+            wallet_client: client(),
+        };
+        clock.on_message(&update, now);
+        assert_eq!(clock.deadline(), Some(deadline));
+        assert_eq!(clock.remaining(now), Some(delay - secs(1)));
+        assert_eq!(clock.remaining(deadline + secs(1)), Some(Duration::ZERO));
+
+        // a pause stops the clock, the resume waits the pending delay again
+        clock.on_message(&AutoBlockMessage::Pause, start + secs(2));
+        assert_eq!(clock.deadline(), None);
+        assert_eq!(clock.remaining(start + secs(2)), None);
+        let resume = AutoBlockMessage::Resume {
+            client: client(),
+            wallet_client: client(),
+        };
+        let now = start + secs(3_600);
+        clock.on_message(&resume, now);
+        assert_eq!(clock.deadline(), Some(now + delay));
+        // resuming a running clock is a no-op
+        clock.on_message(&resume, now + secs(1));
+        assert_eq!(clock.deadline(), Some(now + delay));
+
+        // the next delays are the ones drawn w/o any message
+        for n in 1..100 {
+            let now = start + secs(n * 10_000);
+            clock.mined(now);
+            reference.mined(now);
+            assert_eq!(clock.deadline(), reference.deadline());
+        }
+    }
+
+    #[test]
+    fn subsidy() {
+        let btc = Amount::from_int_btc;
+        assert_eq!(block_subsidy(0, Network::Regtest), btc(50));
+        // regtest halves every 150 blocks
+        assert_eq!(block_subsidy(149, Network::Regtest), btc(50));
+        assert_eq!(block_subsidy(150, Network::Regtest), btc(25));
+        assert_eq!(
+            block_subsidy(300, Network::Regtest),
+            Amount::from_sat(1_250_000_000)
+        );
+        assert_eq!(block_subsidy(64 * 150 - 1, Network::Regtest), Amount::ZERO);
+        assert_eq!(block_subsidy(64 * 150, Network::Regtest), Amount::ZERO);
+        assert_eq!(block_subsidy(209_999, Network::Signet), btc(50));
+        assert_eq!(block_subsidy(210_000, Network::Signet), btc(25));
+    }
+
+    #[test]
+    fn bootstrap() {
+        let btc = Amount::from_int_btc;
+        // the first coinbase matures 101 blocks later
+        assert_eq!(bootstrap_blocks(0, Network::Regtest, btc(1)), Some(101));
+        assert_eq!(bootstrap_blocks(0, Network::Regtest, btc(50)), Some(101));
+        assert_eq!(
+            bootstrap_blocks(0, Network::Regtest, btc(50) + Amount::ONE_SAT),
+            Some(102)
+        );
+        assert_eq!(bootstrap_blocks(0, Network::Regtest, btc(100)), Some(102));
+        // the coinbases at 149 & 150 are across the regtest halving
+        assert_eq!(bootstrap_blocks(148, Network::Regtest, btc(75)), Some(102));
+        assert_eq!(
+            bootstrap_blocks(148, Network::Regtest, btc(75) + Amount::ONE_SAT),
+            Some(103)
+        );
+        // not reachable in MAX_BOOTSTRAP_BLOCKS
+        let reachable = btc(50) * (MAX_BOOTSTRAP_BLOCKS as u64 - COINBASE_MATURITY);
+        assert_eq!(
+            bootstrap_blocks(0, Network::Signet, reachable),
+            Some(MAX_BOOTSTRAP_BLOCKS)
+        );
+        assert_eq!(
+            bootstrap_blocks(0, Network::Signet, reachable + Amount::ONE_SAT),
+            None
+        );
+        // no subsidy anymore
+        assert_eq!(bootstrap_blocks(64 * 150, Network::Regtest, btc(1)), None);
+    }
+
+    #[test]
+    fn censor_criteria() {
+        let descriptor = CensorCriteria::Descriptor {
+            descriptor: String::new(),
+            scripts: [script(1), script(2)].into(),
+        };
+        assert!(descriptor.matches(&txid(0), 1.0, &[script(0), script(2)]));
+        assert!(!descriptor.matches(&txid(0), 1.0, &[script(0), script(3)]));
+        assert!(!descriptor.matches(&txid(0), 1.0, &[]));
+
+        let fee_rate = CensorCriteria::FeeRateBelow(2.0);
+        assert!(fee_rate.matches(&txid(0), 1.99, &[]));
+        assert!(!fee_rate.matches(&txid(0), 2.0, &[]));
+        assert!(!fee_rate.matches(&txid(0), 10.0, &[]));
+
+        let txids = CensorCriteria::Txids([txid(1), txid(2)].into());
+        assert!(txids.matches(&txid(2), 1.0, &[]));
+        assert!(!txids.matches(&txid(3), 1.0, &[]));
+    }
+
+    #[test]
+    fn censor_descendants() {
+        // 1 <- 2 <- 3, 4 <- 5, 6
+        let mempool = [
+            tx(1, 100, &[]),
+            tx(4, 100, &[]),
+            tx(6, 100, &[]),
+            tx(2, 100, &[1]),
+            tx(5, 100, &[4]),
+            tx(3, 100, &[2]),
+        ];
+        let rules = [rule(CensorCriteria::Txids([txid(1)].into()))];
+        let (included, censored) = censored_block(&mempool, &rules);
+        assert_eq!(included, vec![txid(4), txid(6), txid(5)]);
+        assert_eq!(censored, 3);
+
+        // censoring a child keeps its parent
+        let rules = [rule(CensorCriteria::Descriptor {
+            descriptor: String::new(),
+            scripts: [script(5)].into(),
+        })];
+        let (included, censored) = censored_block(&mempool, &rules);
+        assert_eq!(included, vec![txid(1), txid(4), txid(6), txid(2), txid(3)]);
+        assert_eq!(censored, 1);
+
+        let (included, censored) = censored_block(&mempool, &[]);
+        assert_eq!(included.len(), 6);
+        assert_eq!(censored, 0);
+    }
+
+    #[test]
+    fn censor_block_weight() {
+        let max_vsize = MAX_BLOCK_TX_WEIGHT / 4;
+        // 1 fills half of the block, 2 does not fit anymore & 3 spends it, 4 still fits
+        let mempool = [
+            tx(1, max_vsize / 2, &[]),
+            tx(2, max_vsize / 2 + 1, &[]),
+            tx(3, 100, &[2]),
+            tx(4, max_vsize / 2, &[]),
+            tx(5, 1, &[]),
+        ];
+        let (included, censored) = censored_block(&mempool, &[]);
+        assert_eq!(included, vec![txid(1), txid(4)]);
+        // left out, not censored
+        assert_eq!(censored, 0);
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+/// Subset of the `decodepsbt` RPC result.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodedPsbt {
+    pub tx: DecodedTx,
+    pub fee: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodedTx {
+    pub txid: String,
+    pub vin: Vec<DecodedInput>,
+    pub vout: Vec<DecodedOutput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodedInput {
+    pub txid: Option<String>,
+    pub vout: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodedOutput {
+    pub value: f64,
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: DecodedScriptPubKey,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecodedScriptPubKey {
+    pub address: Option<String>,
+    #[serde(rename = "type")]
+    pub script_type: String,
+}
+
+/// Subset of the `analyzepsbt` RPC result.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnalyzedPsbt {
+    #[serde(default)]
+    pub inputs: Vec<AnalyzedInput>,
+    pub next: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnalyzedInput {
+    pub is_final: bool,
+    pub next: Option<String>,
+    pub missing: Option<MissingData>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MissingData {
+    #[serde(default)]
+    pub signatures: Vec<String>,
+    #[serde(default)]
+    pub pubkeys: Vec<String>,
+}
+
+/// Build a human readable report of a PSBT from `decodepsbt` & `analyzepsbt` results.
+pub fn summary(decoded: &DecodedPsbt, analyzed: &AnalyzedPsbt) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "txid: {}", decoded.tx.txid);
+    for (i, input) in decoded.tx.vin.iter().enumerate() {
+        let outpoint = match (&input.txid, input.vout) {
+            (Some(txid), Some(vout)) => format!("{}:{}", txid, vout),
+            _ => "coinbase".to_string(),
+        };
+        let status = match analyzed.inputs.get(i) {
+            Some(AnalyzedInput { is_final: true, .. }) => "final".to_string(),
+            Some(AnalyzedInput { missing, next, .. }) => {
+                let missing = missing.clone().unwrap_or_default();
+                format!(
+                    "next: {}, missing {} signature(s), {} pubkey(s)",
+                    next.as_deref().unwrap_or("?"),
+                    missing.signatures.len(),
+                    missing.pubkeys.len()
+                )
+            }
+            None => "unknown".to_string(),
+        };
+        let _ = writeln!(out, "input #{} {} ({})", i, outpoint, status);
+    }
+    for (i, output) in decoded.tx.vout.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "output #{} {:.8} BTC to {}",
+            i,
+            output.value,
+            output
+                .script_pubkey
+                .address
+                .as_deref()
+                .unwrap_or(&output.script_pubkey.script_type)
+        );
+    }
+    match decoded.fee {
+        Some(fee) => {
+            let _ = writeln!(out, "fee: {:.8} BTC", fee);
+        }
+        None => {
+            let _ = writeln!(out, "fee: unknown (missing utxo data)");
+        }
+    }
+    let _ = write!(out, "next role: {}", analyzed.next);
+    if let Some(error) = &analyzed.error {
+        let _ = write!(out, "\nerror: {}", error);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `decodepsbt` result of a PSBT spending 2 P2WPKH coins, the first one signed &
+    /// finalized (its witness is trimmed)
+    const DECODED: &str = r#"{
+        "tx": {
+            "txid": "7dc1b72083ce6f7bd2c57ebcfed761eb7e00a6ea0a2f3317af8b7f9661bd744b",
+            "hash": "7dc1b72083ce6f7bd2c57ebcfed761eb7e00a6ea0a2f3317af8b7f9661bd744b",
+            "version": 2,
+            "size": 138,
+            "vsize": 138,
+            "weight": 552,
+            "locktime": 0,
+            "vin": [
+                {
+                    "txid": "c7239c24545a850b96d4e20f6a74051d9da3f852757eeb2d5a3a9e54668433ef",
+                    "vout": 1,
+                    "scriptSig": {"asm": "", "hex": ""},
+                    "sequence": 4294967293
+                },
+                {
+                    "txid": "ce5e7cc500a8fcf9e9c9da69afd62e0fd85b49d05d6678563aa0909fc72f72e7",
+                    "vout": 0,
+                    "scriptSig": {"asm": "", "hex": ""},
+                    "sequence": 4294967293
+                }
+            ],
+            "vout": [
+                {
+                    "value": 0.50000000,
+                    "n": 0,
+                    "scriptPubKey": {
+                        "asm": "0 f137976c8b3c739606cab1fb52c8dd815e2d46fe",
+                        "hex": "0014f137976c8b3c739606cab1fb52c8dd815e2d46fe",
+                        "address": "bcrt1q7ymewmyt83eevpk2k8a49jxas90z63h7cmawzv",
+                        "type": "witness_v0_keyhash"
+                    }
+                },
+                {
+                    "value": 0.00000000,
+                    "n": 1,
+                    "scriptPubKey": {
+                        "asm": "OP_RETURN 42424242",
+                        "hex": "6a0442424242",
+                        "type": "nulldata"
+                    }
+                }
+            ]
+        },
+        "global_xpubs": [],
+        "psbt_version": 0,
+        "proprietary": [],
+        "unknown": {},
+        "inputs": [
+            {
+                "witness_utxo": {
+                    "amount": 0.30000000,
+                    "scriptPubKey": {
+                        "asm": "0 517d8587cb3b54d4ede269835e6e5287aba857b4",
+                        "hex": "0014517d8587cb3b54d4ede269835e6e5287aba857b4",
+                        "address": "bcrt1q297ctp7t8d2dfm0zdxp4umjjs746s4a57xef9m",
+                        "type": "witness_v0_keyhash"
+                    }
+                }
+            },
+            {
+                "witness_utxo": {
+                    "amount": 0.20001410,
+                    "scriptPubKey": {
+                        "asm": "0 69d53a5ae55a1e96c5272dd51bf5b94660b07c64",
+                        "hex": "001469d53a5ae55a1e96c5272dd51bf5b94660b07c64",
+                        "address": "bcrt1qd82n5kh9tg0fd3f89h23hadegestqlrydfl720",
+                        "type": "witness_v0_keyhash"
+                    }
+                }
+            }
+        ],
+        "outputs": [{}, {}],
+        "fee": 0.00001410
+    }"#;
+
+    /// `analyzepsbt` result of the same PSBT, the second input misses the signature of
+    /// the key hashed in its script
+    const ANALYZED: &str = r#"{
+        "inputs": [
+            {"has_utxo": true, "is_final": true, "next": "extractor"},
+            {
+                "has_utxo": true,
+                "is_final": false,
+                "missing": {
+                    "signatures": ["69d53a5ae55a1e96c5272dd51bf5b94660b07c64"]
+                },
+                "next": "signer"
+            }
+        ],
+        "estimated_vsize": 193,
+        "estimated_feerate": 0.00007305,
+        "fee": 0.00001410,
+        "next": "signer"
+    }"#;
+
+    #[test]
+    fn psbt_summary() {
+        let decoded: DecodedPsbt = serde_json::from_str(DECODED).unwrap();
+        let analyzed: AnalyzedPsbt = serde_json::from_str(ANALYZED).unwrap();
+        let expected = [
+            "txid: 7dc1b72083ce6f7bd2c57ebcfed761eb7e00a6ea0a2f3317af8b7f9661bd744b",
+            "input #0 c7239c24545a850b96d4e20f6a74051d9da3f852757eeb2d5a3a9e54668433ef:1 (final)",
+            "input #1 ce5e7cc500a8fcf9e9c9da69afd62e0fd85b49d05d6678563aa0909fc72f72e7:0 (next: \
+             signer, missing 1 signature(s), 0 pubkey(s))",
+            "output #0 0.50000000 BTC to bcrt1q7ymewmyt83eevpk2k8a49jxas90z63h7cmawzv",
+            // no address, the script type
+            "output #1 0.00000000 BTC to nulldata",
+            "fee: 0.00001410 BTC",
+            "next role: signer",
+        ];
+        assert_eq!(summary(&decoded, &analyzed), expected.join("\n"));
+    }
+
+    #[test]
+    fn psbt_summary_wo_utxo() {
+        let mut decoded: DecodedPsbt = serde_json::from_str(DECODED).unwrap();
+        decoded.fee = None;
+        // analyzepsbt returns no inputs on error
+        let analyzed: AnalyzedPsbt = serde_json::from_str(
+            r#"{"next": "updater", "error": "PSBT is not valid. Input 0 spends unspendable output"}"#,
+        )
+        .unwrap();
+        let summary = summary(&decoded, &analyzed);
+        let lines = summary.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[1],
+            "input #0 c7239c24545a850b96d4e20f6a74051d9da3f852757eeb2d5a3a9e54668433ef:1 (unknown)"
+        );
+        assert!(lines.contains(&"fee: unknown (missing utxo data)"));
+        assert!(lines.contains(&"next role: updater"));
+        assert_eq!(
+            lines.last(),
+            Some(&"error: PSBT is not valid. Input 0 spends unspendable output")
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use miniscript::bitcoin::{consensus::encode, Address, Network, Transaction};
+
+/// Decode a raw transaction from its hex serialization.
+pub fn decode(hex: &str) -> Result<Transaction, String> {
+    encode::deserialize_hex::<Transaction>(hex.trim()).map_err(|e| e.to_string())
+}
+
+/// Build a human readable report of a transaction.
+pub fn summary(tx: &Transaction, network: Network) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "txid: {}", tx.compute_txid());
+    let _ = writeln!(out, "wtxid: {}", tx.compute_wtxid());
+    let _ = writeln!(
+        out,
+        "version: {} locktime: {} size: {} vsize: {} weight: {}",
+        tx.version.0,
+        tx.lock_time,
+        tx.total_size(),
+        tx.vsize(),
+        tx.weight().to_wu()
+    );
+    for (i, input) in tx.input.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "input #{} {} sequence: {:#x}",
+            i,
+            input.previous_output,
+            input.sequence.to_consensus_u32()
+        );
+    }
+    for (i, output) in tx.output.iter().enumerate() {
+        let destination = match Address::from_script(&output.script_pubkey, network) {
+            Ok(address) => address.to_string(),
+            Err(_) => output.script_pubkey.to_hex_string(),
+        };
+        let _ = writeln!(out, "output #{} {} to {}", i, output.value, destination);
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// v2 tx spending 1111..11:1 (p2wpkh witness), paying 50_000 sat to a p2wpkh and an
+    /// OP_RETURN, locktime 150
+    const TX: &str = "0200000000010111111111111111111111111111111111111111111111111111111111111111110100000000fdffffff0250c300000000000016001422222222222222222222222222222222222222220000000000000000066a0442424242024730303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030302102020202020202020202020202020202020202020202020202020202020202020296000000";
+
+    #[test]
+    fn decode_tx() {
+        let tx = decode(&format!("  {}\n", TX)).unwrap();
+        assert_eq!(
+            tx.compute_txid().to_string(),
+            "d5764d0bbb8079755c083bc0ce1ef0f58509253fb633bd1b20dfe6003fe41188"
+        );
+        assert_eq!(tx.input.len(), 1);
+        assert_eq!(tx.input[0].witness.len(), 2);
+        assert_eq!(tx.output.len(), 2);
+
+        assert!(decode("").is_err());
+        assert!(decode("zz").is_err());
+        // truncated
+        assert!(decode(&TX[..TX.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn tx_summary() {
+        let tx = decode(TX).unwrap();
+        let expected = [
+            "txid: d5764d0bbb8079755c083bc0ce1ef0f58509253fb633bd1b20dfe6003fe41188",
+            "wtxid: 316a16715434168b39b7ad03b9e7f317d50f4c82e185e0a4e11849fad3babc20",
+            "version: 2 locktime: 150 size: 206 vsize: 125 weight: 497",
+            "input #0 1111111111111111111111111111111111111111111111111111111111111111:1 sequence: 0xfffffffd",
+            "output #0 0.00050000 BTC to bcrt1qyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zs4w3j0",
+            // not an address
+            "output #1 0 BTC to 6a0442424242",
+        ];
+        assert_eq!(summary(&tx, Network::Regtest), expected.join("\n"));
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use miniscript::{
+    bitcoin::{Address, Amount},
+    Descriptor, DescriptorPublicKey,
+};
+
+pub type ScheduleId = u64;
+
+/// Where the payments of a schedule go.
+#[derive(Debug, Clone)]
+pub enum ScheduleTarget {
+    /// Loop over a list of (deposit) addresses
+    Addresses(Vec<Address>),
+    /// Derive a new address from the descriptor for every payment
+    Descriptor {
+        descriptor: Descriptor<DescriptorPublicKey>,
+        start_index: u32,
+    },
+    /// A new random address (burned coins) for every payment
+    Random,
+}
+
+impl Display for ScheduleTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleTarget::Addresses(addresses) => write!(f, "{} address(es)", addresses.len()),
+            ScheduleTarget::Descriptor { start_index, .. } => {
+                write!(f, "descriptor from index {}", start_index)
+            }
+            ScheduleTarget::Random => write!(f, "random addresses"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Frequency {
+    /// One payment every N blocks
+    Blocks(u32),
+    /// One payment every interval
+    Interval(Duration),
+}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Frequency::Blocks(blocks) => write!(f, "every {} block(s)", blocks),
+            Frequency::Interval(interval) => write!(f, "every {}s", interval.as_secs()),
+        }
+    }
+}
+
+/// A recurring payment from the controlled wallet.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub target: ScheduleTarget,
+    pub amount_min: Amount,
+    pub amount_max: Amount,
+    pub frequency: Frequency,
+}
+
+/// A schedule registered in the service.
+#[derive(Debug, Clone)]
+pub struct ScheduledSend {
+    pub id: ScheduleId,
+    pub schedule: Schedule,
+    /// Payments sent so far
+    pub sent: u32,
+    /// Height & time of the last payment (or of the schedule creation)
+    last_height: u64,
+    last_time: Instant,
+}
+
+impl ScheduledSend {
+    pub fn new(id: ScheduleId, schedule: Schedule, height: u64) -> Self {
+        ScheduledSend {
+            id,
+            schedule,
+            sent: 0,
+            last_height: height,
+            last_time: Instant::now(),
+        }
+    }
+
+    /// Whether a payment is due at `height`.
+    pub fn due(&self, height: u64, now: Instant) -> bool {
+        match self.schedule.frequency {
+            Frequency::Blocks(blocks) => height >= self.last_height + blocks.max(1) as u64,
+            Frequency::Interval(interval) => now >= self.last_time + interval,
+        }
+    }
+
+    /// Record a payment attempt, failed ones included so a failing schedule does not
+    /// retry on every tick.
+    pub fn attempted(&mut self, height: u64, now: Instant, sent: bool) {
+        if sent {
+            self.sent += 1;
+        }
+        self.last_height = height;
+        self.last_time = now;
+    }
+
+    /// Position in the address list or descriptor index of the next payment.
+    pub fn next_index(&self) -> u32 {
+        match &self.schedule.target {
+            ScheduleTarget::Descriptor { start_index, .. } => start_index + self.sent,
+            _ => self.sent,
+        }
+    }
+}
+
+impl Display for ScheduledSend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} - {} to {} {} ({} sent)",
+            self.id,
+            self.schedule.amount_min,
+            self.schedule.amount_max,
+            self.schedule.target,
+            self.schedule.frequency,
+            self.sent
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn scheduled(target: ScheduleTarget, frequency: Frequency) -> ScheduledSend {
+        let schedule = Schedule {
+            target,
+            amount_min: Amount::from_sat(1_000),
+            amount_max: Amount::from_sat(2_000),
+            frequency,
+        };
+        ScheduledSend::new(0, schedule, 100)
+    }
+
+    #[test]
+    fn due_every_blocks() {
+        let now = Instant::now();
+        let mut send = scheduled(ScheduleTarget::Random, Frequency::Blocks(3));
+        assert!(!send.due(101, now));
+        assert!(!send.due(102, now));
+        assert!(send.due(103, now));
+        // the time is not considered
+        assert!(!send.due(102, now + Duration::from_secs(3600)));
+        send.attempted(103, now, true);
+        assert!(!send.due(105, now));
+        assert!(send.due(106, now));
+
+        // 0 blocks is handled as 1 block
+        let send = scheduled(ScheduleTarget::Random, Frequency::Blocks(0));
+        assert!(!send.due(100, now));
+        assert!(send.due(101, now));
+    }
+
+    #[test]
+    fn due_every_interval() {
+        let interval = Duration::from_secs(60);
+        let mut send = scheduled(ScheduleTarget::Random, Frequency::Interval(interval));
+        let start = send.last_time;
+        assert!(!send.due(100, start + Duration::from_secs(59)));
+        assert!(send.due(100, start + interval));
+        // the height is not considered
+        assert!(!send.due(1_000, start));
+        send.attempted(100, start + interval, true);
+        assert!(!send.due(100, start + Duration::from_secs(119)));
+        assert!(send.due(100, start + 2 * interval));
+    }
+
+    #[test]
+    fn failed_attempts() {
+        let now = Instant::now();
+        let mut send = scheduled(ScheduleTarget::Random, Frequency::Blocks(1));
+        assert_eq!(send.next_index(), 0);
+        send.attempted(101, now, true);
+        assert_eq!((send.sent, send.next_index()), (1, 1));
+        // a failed attempt delays the next one but does not advance the index
+        send.attempted(102, now, false);
+        assert_eq!((send.sent, send.next_index()), (1, 1));
+        assert!(!send.due(102, now));
+        assert!(send.due(103, now));
+        send.attempted(103, now, true);
+        assert_eq!((send.sent, send.next_index()), (2, 2));
+    }
+
+    #[test]
+    fn descriptor_start_index() {
+        const DESCRIPTOR: &str = "wpkh([9c32dc88/48'/1'/0'/2']tpubDEUUVSJyh6t12FbNhmmYa1M39AiD2VKGBaGT54aPz2xVF5Kg1dx3XSb5T4nKBakEz8ypy35fYVAZgBc7MVwQ2qEZEZRqDbvDu8w5AZVu4q2/0/*)";
+        let target = ScheduleTarget::Descriptor {
+            descriptor: Descriptor::from_str(DESCRIPTOR).unwrap(),
+            start_index: 10,
+        };
+        let now = Instant::now();
+        let mut send = scheduled(target, Frequency::Blocks(1));
+        assert_eq!(send.next_index(), 10);
+        send.attempted(101, now, true);
+        assert_eq!(send.next_index(), 11);
+        send.attempted(102, now, false);
+        assert_eq!(send.next_index(), 11);
+    }
+}
@@ -1,11 +1,9 @@
-use std::sync::mpsc;
-
 #[allow(async_fn_in_trait)]
 pub trait ServiceFn<Message> {
     fn new(
         sender: async_channel::Sender<Message>,
-        receiver: mpsc::Receiver<Message>,
-        loopback: mpsc::Sender<Message>,
+        receiver: async_channel::Receiver<Message>,
+        loopback: async_channel::Sender<Message>,
     ) -> Self;
     async fn run(&mut self);
 }
@@ -0,0 +1,600 @@
+use std::str::FromStr;
+
+use bitcoincore_rpc::{Client, RpcApi};
+use miniscript::bitcoin::{
+    absolute::LockTime,
+    block::{Header, Version as BlockVersion},
+    consensus::{encode, Encodable},
+    ecdsa,
+    hashes::Hash,
+    opcodes::{
+        all::{OP_PUSHNUM_1, OP_RETURN},
+        OP_0,
+    },
+    script::{Builder, Instruction, PushBytesBuf},
+    secp256k1::{All, Message, Secp256k1},
+    sighash::{EcdsaSighashType, SighashCache},
+    transaction::Version,
+    Address, Amount, Block, BlockHash, CompactTarget, OutPoint, PrivateKey, ScriptBuf, Sequence,
+    Transaction, TxIn, TxMerkleNode, TxOut, Witness,
+};
+use serde::Deserialize;
+
+use crate::error::Error;
+
+/// BIP325 signet commitment header
+const SIGNET_HEADER: [u8; 4] = [0xec, 0xc7, 0xda, 0xa2];
+
+/// Subset of the `getblocktemplate` RPC result.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockTemplate {
+    pub version: i32,
+    pub previousblockhash: String,
+    pub transactions: Vec<TemplateTransaction>,
+    pub coinbasevalue: u64,
+    pub bits: String,
+    pub height: u64,
+    pub curtime: u32,
+    pub mintime: u32,
+    pub default_witness_commitment: Option<String>,
+    pub signet_challenge: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateTransaction {
+    pub data: String,
+}
+
+fn signet_error<E: std::fmt::Display>(e: E) -> Error {
+    Error::Signet(e.to_string())
+}
+
+pub fn block_template(client: &Client) -> Result<BlockTemplate, Error> {
+    client
+        .call::<BlockTemplate>(
+            "getblocktemplate",
+            &[serde_json::json!({"rules": ["segwit", "signet"]})],
+        )
+        .map_err(Error::from)
+}
+
+fn challenge(template: &BlockTemplate) -> Result<ScriptBuf, Error> {
+    let challenge = template
+        .signet_challenge
+        .as_ref()
+        .ok_or_else(|| Error::Signet("the node is not a signet node".to_string()))?;
+    ScriptBuf::from_hex(challenge).map_err(signet_error)
+}
+
+/// Check `key` can sign blocks for the signet challenge of the node.
+pub fn check_key(client: &Client, secp: &Secp256k1<All>, key: &PrivateKey) -> Result<(), Error> {
+    let challenge = challenge(&block_template(client)?)?;
+    check_challenge(secp, key, &challenge)
+}
+
+/// Signet challenges `sign` can solve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChallengeKind {
+    P2wpkh,
+    P2pk,
+    /// 1-of-n bare multisig
+    Multisig,
+}
+
+fn challenge_kind(challenge: &ScriptBuf) -> Result<ChallengeKind, Error> {
+    if challenge.is_p2wpkh() {
+        Ok(ChallengeKind::P2wpkh)
+    } else if challenge.is_p2pk() {
+        Ok(ChallengeKind::P2pk)
+    } else if challenge.is_multisig() {
+        // a single signature is produced
+        if challenge.as_bytes().first() == Some(&OP_PUSHNUM_1.to_u8()) {
+            Ok(ChallengeKind::Multisig)
+        } else {
+            Err(Error::Signet(format!(
+                "only 1-of-n multisig signet challenges are supported, got {}",
+                challenge.to_hex_string()
+            )))
+        }
+    } else {
+        Err(Error::Signet(format!(
+            "unsupported signet challenge {}",
+            challenge.to_hex_string()
+        )))
+    }
+}
+
+fn check_challenge(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    challenge: &ScriptBuf,
+) -> Result<(), Error> {
+    let pubkey = key.public_key(secp);
+    let matches = match challenge_kind(challenge)? {
+        ChallengeKind::P2wpkh => pubkey
+            .wpubkey_hash()
+            .map(|hash| ScriptBuf::new_p2wpkh(&hash) == *challenge)
+            .unwrap_or(false),
+        ChallengeKind::P2pk => challenge.p2pk_public_key() == Some(pubkey),
+        ChallengeKind::Multisig => {
+            let pubkey = pubkey.to_bytes();
+            challenge.instructions().any(|i| match i {
+                Ok(Instruction::PushBytes(bytes)) => bytes.as_bytes() == pubkey.as_slice(),
+                _ => false,
+            })
+        }
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(Error::Signet(format!(
+            "the key does not match the signet challenge {}",
+            challenge.to_hex_string()
+        )))
+    }
+}
+
+/// BIP325 `to_spend` & (unsigned) `to_sign` transactions of `block`.
+/// The signet commitment of the coinbase of `block` must only contain the header.
+fn signet_txs(challenge: &ScriptBuf, block: &Block) -> Result<(Transaction, Transaction), Error> {
+    let merkle_root = block
+        .compute_merkle_root()
+        .ok_or_else(|| Error::Signet("empty block".to_string()))?;
+    let mut block_data = Vec::new();
+    block
+        .header
+        .version
+        .consensus_encode(&mut block_data)
+        .map_err(signet_error)?;
+    block
+        .header
+        .prev_blockhash
+        .consensus_encode(&mut block_data)
+        .map_err(signet_error)?;
+    merkle_root
+        .consensus_encode(&mut block_data)
+        .map_err(signet_error)?;
+    block
+        .header
+        .time
+        .consensus_encode(&mut block_data)
+        .map_err(signet_error)?;
+
+    let to_spend = Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: Builder::new()
+                .push_opcode(OP_0)
+                .push_slice(PushBytesBuf::try_from(block_data).map_err(signet_error)?)
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: challenge.clone(),
+        }],
+    };
+
+    let to_sign = Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: to_spend.compute_txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    };
+    Ok((to_spend, to_sign))
+}
+
+/// Build the signet solution (serialized scriptSig + witness) signing `block`.
+/// The signet commitment of the coinbase of `block` must only contain the header.
+fn sign(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    challenge: &ScriptBuf,
+    block: &Block,
+) -> Result<Vec<u8>, Error> {
+    let kind = challenge_kind(challenge)?;
+    let (_, mut to_sign) = signet_txs(challenge, block)?;
+    let pubkey = key.public_key(secp);
+    let mut cache = SighashCache::new(&to_sign);
+    if kind == ChallengeKind::P2wpkh {
+        let sighash = cache
+            .p2wpkh_signature_hash(0, challenge, Amount::ZERO, EcdsaSighashType::All)
+            .map_err(signet_error)?;
+        let signature = ecdsa::Signature::sighash_all(
+            secp.sign_ecdsa(&Message::from_digest(sighash.to_byte_array()), &key.inner),
+        );
+        to_sign.input[0].witness = Witness::p2wpkh(&signature, &pubkey.inner);
+    } else {
+        let sighash = cache
+            .legacy_signature_hash(0, challenge, EcdsaSighashType::All.to_u32())
+            .map_err(signet_error)?;
+        let signature = ecdsa::Signature::sighash_all(
+            secp.sign_ecdsa(&Message::from_digest(sighash.to_byte_array()), &key.inner),
+        );
+        let signature = PushBytesBuf::try_from(signature.to_vec()).map_err(signet_error)?;
+        to_sign.input[0].script_sig = if kind == ChallengeKind::Multisig {
+            // OP_0 for the CHECKMULTISIG off-by-one
+            Builder::new()
+                .push_opcode(OP_0)
+                .push_slice(signature)
+                .into_script()
+        } else {
+            Builder::new().push_slice(signature).into_script()
+        };
+    }
+
+    let mut solution = Vec::new();
+    to_sign.input[0]
+        .script_sig
+        .consensus_encode(&mut solution)
+        .map_err(signet_error)?;
+    to_sign.input[0]
+        .witness
+        .consensus_encode(&mut solution)
+        .map_err(signet_error)?;
+    Ok(solution)
+}
+
+/// Assemble a block from the node template paying to `address`, sign it w/ `key`,
+/// grind the proof of work and submit it.
+pub fn mine_block(
+    client: &Client,
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    address: &Address,
+) -> Result<BlockHash, Error> {
+    let template = block_template(client)?;
+    let challenge = challenge(&template)?;
+    let commitment = template
+        .default_witness_commitment
+        .as_ref()
+        .ok_or_else(|| Error::Signet("template w/o witness commitment".to_string()))
+        .and_then(|c| ScriptBuf::from_hex(c).map_err(signet_error))?;
+
+    let coinbase = Transaction {
+        version: Version::ONE,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            // BIP34 height
+            script_sig: Builder::new()
+                .push_int(template.height as i64)
+                .push_opcode(OP_0)
+                .into_script(),
+            sequence: Sequence::MAX,
+            // witness reserved value
+            witness: Witness::from_slice(&[[0u8; 32]]),
+        }],
+        output: vec![
+            TxOut {
+                value: Amount::from_sat(template.coinbasevalue),
+                script_pubkey: address.script_pubkey(),
+            },
+            TxOut {
+                value: Amount::ZERO,
+                script_pubkey: commitment.clone(),
+            },
+        ],
+    };
+
+    let mut txdata = vec![coinbase];
+    for tx in &template.transactions {
+        txdata.push(encode::deserialize_hex::<Transaction>(&tx.data).map_err(signet_error)?);
+    }
+
+    let mut block = Block {
+        header: Header {
+            version: BlockVersion::from_consensus(template.version),
+            prev_blockhash: BlockHash::from_str(&template.previousblockhash)
+                .map_err(signet_error)?,
+            merkle_root: TxMerkleNode::all_zeros(),
+            time: template.curtime.max(template.mintime),
+            bits: CompactTarget::from_consensus(
+                u32::from_str_radix(&template.bits, 16).map_err(signet_error)?,
+            ),
+            nonce: 0,
+        },
+        txdata,
+    };
+
+    // the time is signed, it is bumped & the block signed again if the nonces run out
+    loop {
+        commit_solution(secp, key, &challenge, &commitment, &mut block)?;
+        if grind(&mut block.header) {
+            break;
+        }
+        block.header.time += 1;
+    }
+
+    let hash = block.block_hash();
+    let result = client
+        .call::<serde_json::Value>("submitblock", &[encode::serialize_hex(&block).into()])
+        .map_err(Error::from)?;
+    match result.as_str() {
+        // submitblock returns null on success
+        None => Ok(hash),
+        Some(reason) => Err(Error::BlockRejected(reason.to_string())),
+    }
+}
+
+/// Sign `block` and set its signet commitment, an extra push in the witness commitment
+/// output `commitment` (the signed block commits to this push w/ the header only).
+fn commit_solution(
+    secp: &Secp256k1<All>,
+    key: &PrivateKey,
+    challenge: &ScriptBuf,
+    commitment: &ScriptBuf,
+    block: &mut Block,
+) -> Result<(), Error> {
+    let with_push = |data: Vec<u8>| -> Result<ScriptBuf, Error> {
+        Ok(Builder::from(commitment.to_bytes())
+            .push_slice(PushBytesBuf::try_from(data).map_err(signet_error)?)
+            .into_script())
+    };
+    block.txdata[0].output[1].script_pubkey = with_push(SIGNET_HEADER.to_vec())?;
+    let solution = sign(secp, key, challenge, block)?;
+    let mut data = SIGNET_HEADER.to_vec();
+    data.extend(solution);
+    block.txdata[0].output[1].script_pubkey = with_push(data)?;
+    block.header.merkle_root = block
+        .compute_merkle_root()
+        .ok_or_else(|| Error::Signet("empty block".to_string()))?;
+    Ok(())
+}
+
+/// Grind the nonce of `header` from 0, false if no nonce meets the target.
+fn grind(header: &mut Header) -> bool {
+    let target = header.target();
+    header.nonce = 0;
+    while header.validate_pow(target).is_err() {
+        match header.nonce.checked_add(1) {
+            Some(nonce) => header.nonce = nonce,
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use miniscript::bitcoin::{
+        consensus::Decodable,
+        opcodes::all::{OP_CHECKMULTISIG, OP_PUSHNUM_2},
+        secp256k1::ecdsa::Signature,
+        Network, PublicKey,
+    };
+
+    use super::*;
+
+    /// Default signet challenge (1-of-2 multisig), see bitcoin/src/kernel/chainparams.cpp
+    const DEFAULT_CHALLENGE: &str = "512103ad5e0edad18cb1f0fc0d28a3d4f1f3e445640337489abb10404f2d1e086be430210359ef5021964fe22d6f8e05b2463c9540ce96883fe3b278760f048f5189f2e6c452ae";
+
+    fn private_key(byte: u8) -> PrivateKey {
+        PrivateKey::from_slice(&[byte; 32], Network::Signet).unwrap()
+    }
+
+    /// `required`-of-2 bare multisig
+    fn multisig(required: u8, keys: [&PublicKey; 2]) -> ScriptBuf {
+        let required = if required == 1 {
+            OP_PUSHNUM_1
+        } else {
+            OP_PUSHNUM_2
+        };
+        Builder::new()
+            .push_opcode(required)
+            .push_key(keys[0])
+            .push_key(keys[1])
+            .push_opcode(OP_PUSHNUM_2)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script()
+    }
+
+    /// Block w/ a coinbase whose signet commitment only contains the header.
+    fn block() -> Block {
+        let commitment = ScriptBuf::from_hex(
+            "6a24aa21a9ed0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let coinbase = Transaction {
+            version: Version::ONE,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig: Builder::new().push_int(1).push_opcode(OP_0).into_script(),
+                sequence: Sequence::MAX,
+                witness: Witness::from_slice(&[[0u8; 32]]),
+            }],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(5_000_000_000),
+                    script_pubkey: ScriptBuf::new(),
+                },
+                TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: Builder::from(commitment.to_bytes())
+                        .push_slice(SIGNET_HEADER)
+                        .into_script(),
+                },
+            ],
+        };
+        let mut block = Block {
+            header: Header {
+                version: BlockVersion::from_consensus(0x2000_0000),
+                prev_blockhash: BlockHash::from_byte_array([1; 32]),
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: 1_700_000_000,
+                bits: CompactTarget::from_consensus(0x1e03_77ae),
+                nonce: 0,
+            },
+            txdata: vec![coinbase],
+        };
+        block.header.merkle_root = block.compute_merkle_root().unwrap();
+        block
+    }
+
+    /// Split a solution in its scriptSig & witness.
+    fn decode_solution(solution: &[u8]) -> (ScriptBuf, Witness) {
+        let mut cursor = solution;
+        let script_sig = ScriptBuf::consensus_decode(&mut cursor).unwrap();
+        let witness = Witness::consensus_decode(&mut cursor).unwrap();
+        assert!(cursor.is_empty(), "trailing solution bytes");
+        (script_sig, witness)
+    }
+
+    fn verify(secp: &Secp256k1<All>, sighash: [u8; 32], signature: &[u8], key: &PublicKey) {
+        let (sighash_type, signature) = signature.split_last().unwrap();
+        assert_eq!(*sighash_type, EcdsaSighashType::All as u8);
+        let signature = Signature::from_der(signature).unwrap();
+        secp.verify_ecdsa(&Message::from_digest(sighash), &signature, &key.inner)
+            .unwrap();
+    }
+
+    #[test]
+    fn signet_transactions() {
+        let block = block();
+        let challenge = ScriptBuf::from_hex(DEFAULT_CHALLENGE).unwrap();
+        let (to_spend, to_sign) = signet_txs(&challenge, &block).unwrap();
+
+        // OP_0 PUSH72 version | prev block hash | merkle root | time
+        let mut script_sig = "0048".to_string();
+        script_sig.push_str("00000020");
+        script_sig.push_str(&"01".repeat(32));
+        script_sig.push_str(&encode::serialize_hex(&block.header.merkle_root));
+        script_sig.push_str("00f15365");
+        assert_eq!(to_spend.input[0].script_sig.to_hex_string(), script_sig);
+        assert_eq!(to_spend.version, Version(0));
+        assert_eq!(to_spend.lock_time, LockTime::ZERO);
+        assert_eq!(to_spend.input[0].previous_output, OutPoint::null());
+        assert_eq!(to_spend.input[0].sequence, Sequence::ZERO);
+        assert_eq!(to_spend.output[0].value, Amount::ZERO);
+        assert_eq!(to_spend.output[0].script_pubkey, challenge);
+        assert_eq!(
+            to_spend.compute_txid().to_string(),
+            "a2e349abfc7350627a29c0a462ae6b9448b00207fbec17428189119b4b8922ce"
+        );
+
+        assert_eq!(to_sign.version, Version(0));
+        assert_eq!(to_sign.lock_time, LockTime::ZERO);
+        assert_eq!(
+            to_sign.input[0].previous_output,
+            OutPoint::new(to_spend.compute_txid(), 0)
+        );
+        assert_eq!(to_sign.input[0].sequence, Sequence::ZERO);
+        assert_eq!(to_sign.output[0].value, Amount::ZERO);
+        assert_eq!(to_sign.output[0].script_pubkey.to_hex_string(), "6a");
+    }
+
+    #[test]
+    fn sign_p2wpkh() {
+        let secp = Secp256k1::new();
+        let key = private_key(1);
+        let pubkey = key.public_key(&secp);
+        let challenge = ScriptBuf::new_p2wpkh(&pubkey.wpubkey_hash().unwrap());
+        let block = block();
+
+        let solution = sign(&secp, &key, &challenge, &block).unwrap();
+        assert_eq!(hex::encode(&solution), "000247304402207d5ab3265bb2086a11a8adccf9d2ce362fccadbf4db1ba949ed6d168e38f6f0e0220777382f968f3846190509995c1de143100bf1c8d4f5c030a645f4bf5819452b20121031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f");
+
+        // empty scriptSig, witness <signature> <pubkey>
+        let (script_sig, witness) = decode_solution(&solution);
+        assert!(script_sig.is_empty());
+        assert_eq!(witness.len(), 2);
+        assert_eq!(witness.nth(1).unwrap(), pubkey.to_bytes().as_slice());
+        let (_, to_sign) = signet_txs(&challenge, &block).unwrap();
+        let sighash = SighashCache::new(&to_sign)
+            .p2wpkh_signature_hash(0, &challenge, Amount::ZERO, EcdsaSighashType::All)
+            .unwrap();
+        verify(
+            &secp,
+            sighash.to_byte_array(),
+            witness.nth(0).unwrap(),
+            &pubkey,
+        );
+    }
+
+    #[test]
+    fn sign_multisig() {
+        let secp = Secp256k1::new();
+        let key = private_key(1);
+        let pubkey = key.public_key(&secp);
+        let challenge = multisig(1, [&private_key(2).public_key(&secp), &pubkey]);
+        let block = block();
+
+        let solution = sign(&secp, &key, &challenge, &block).unwrap();
+        assert_eq!(hex::encode(&solution), "4a00483045022100d79171a7d6e1dd6b28a029dafbd4a8c068a90879036e86761238eb2ffb303f4b02200758cf3bb1540fafd40a2ff03567991ea4e48e1696b99720857be1f09e7756400100");
+
+        // scriptSig OP_0 <signature>, empty witness
+        let (script_sig, witness) = decode_solution(&solution);
+        assert!(witness.is_empty());
+        let instructions = script_sig
+            .instructions()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(instructions.len(), 2);
+        // OP_0 is an empty push
+        assert!(instructions[0].push_bytes().unwrap().is_empty());
+        let signature = instructions[1].push_bytes().unwrap().as_bytes();
+        let (_, to_sign) = signet_txs(&challenge, &block).unwrap();
+        let sighash = SighashCache::new(&to_sign)
+            .legacy_signature_hash(0, &challenge, EcdsaSighashType::All.to_u32())
+            .unwrap();
+        verify(&secp, sighash.to_byte_array(), signature, &pubkey);
+    }
+
+    #[test]
+    fn challenge_keys() {
+        let secp = Secp256k1::new();
+        let (key, other) = (private_key(1), private_key(2));
+        let pubkey = key.public_key(&secp);
+        let other_pubkey = other.public_key(&secp);
+
+        let p2wpkh = ScriptBuf::new_p2wpkh(&pubkey.wpubkey_hash().unwrap());
+        assert!(check_challenge(&secp, &key, &p2wpkh).is_ok());
+        assert!(check_challenge(&secp, &other, &p2wpkh).is_err());
+
+        let p2pk = ScriptBuf::new_p2pk(&pubkey);
+        assert!(check_challenge(&secp, &key, &p2pk).is_ok());
+        assert!(check_challenge(&secp, &other, &p2pk).is_err());
+
+        let one_of_two = multisig(1, [&other_pubkey, &pubkey]);
+        assert!(check_challenge(&secp, &key, &one_of_two).is_ok());
+        assert!(check_challenge(&secp, &other, &one_of_two).is_ok());
+        assert!(check_challenge(&secp, &private_key(3), &one_of_two).is_err());
+
+        // only one signature is produced
+        let two_of_two = multisig(2, [&other_pubkey, &pubkey]);
+        assert!(check_challenge(&secp, &key, &two_of_two).is_err());
+        assert!(sign(&secp, &key, &two_of_two, &block()).is_err());
+
+        let default = ScriptBuf::from_hex(DEFAULT_CHALLENGE).unwrap();
+        assert_eq!(challenge_kind(&default).unwrap(), ChallengeKind::Multisig);
+        assert!(check_challenge(&secp, &key, &default).is_err());
+
+        let unsupported = Builder::new().push_int(1).into_script();
+        assert!(check_challenge(&secp, &key, &unsupported).is_err());
+    }
+
+    #[test]
+    fn grind_nonce() {
+        let mut header = block().header;
+        // regtest target
+        header.bits = CompactTarget::from_consensus(0x207f_ffff);
+        header.nonce = 42;
+        assert!(grind(&mut header));
+        assert!(header.validate_pow(header.target()).is_ok());
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use bitcoincore_rpc::json;
+use miniscript::bitcoin::Amount;
+use rand::Rng;
+
+/// Max mean count of transactions sent in one block.
+pub const MAX_TX_PER_BLOCK: f64 = 10_000.0;
+/// Poisson draws are split in chunks of this mean at most, so `exp(-mean)` does not
+/// underflow.
+const POISSON_CHUNK: f64 = 30.0;
+
+/// Count of transactions to send in one block, to send on average `count` transactions
+/// every `blocks` blocks.
+///
+/// The count is Poisson distributed w/ mean `count / blocks` (capped to
+/// `MAX_TX_PER_BLOCK`), i.e. the transactions arrive independently at a constant rate, like
+/// on a real chain. `blocks == 0` is handled as 1 block.
+pub fn tx_count<R: Rng>(rng: &mut R, count: u32, blocks: u32) -> u32 {
+    let mean = (count as f64 / blocks.max(1) as f64).min(MAX_TX_PER_BLOCK);
+    poisson(rng, mean)
+}
+
+/// Draw from a Poisson distribution w/ mean `mean`.
+///
+/// Knuth's multiplication method, the sum of independent Poisson variables being
+/// Poisson distributed, large means are drawn as a sum of chunks of mean `POISSON_CHUNK`.
+pub fn poisson<R: Rng>(rng: &mut R, mean: f64) -> u32 {
+    let mut left = mean.max(0.0);
+    let mut count = 0;
+    while left > 0.0 {
+        let chunk = left.min(POISSON_CHUNK);
+        left -= chunk;
+        let limit = (-chunk).exp();
+        let mut product: f64 = rng.gen();
+        while product > limit {
+            count += 1;
+            product *= rng.gen::<f64>();
+        }
+    }
+    count
+}
+
+/// Uniformly distributed amount in `[min, max]`, the bounds are swapped if inverted and
+/// `min` is returned if they are equal.
+pub fn amount<R: Rng>(rng: &mut R, min: Amount, max: Amount) -> Amount {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    Amount::from_sat(rng.gen_range(min.to_sat()..=max.to_sat()))
+}
+
+/// Runs seeded from the main seed.
+#[derive(Debug, Clone, Copy)]
+pub enum SeedStream {
+    Job = 1,
+    AutoBlock = 2,
+    /// Block intervals of an autoblock run, apart from its payout draws
+    BlockInterval = 3,
+}
+
+/// Seed of the run `id` of `stream`, derived from the main `seed` only, so a run replays
+/// the same w/ the same main seed whatever ran (and drew from the service generator)
+/// before.
+pub fn derive_seed(seed: u64, stream: SeedStream, id: u64) -> u64 {
+    splitmix64(splitmix64(seed ^ stream as u64) ^ id)
+}
+
+/// Seed of a job as printed and typed in the `jobs` panel: `<main seed>/<nth job>`, typed
+/// back the next job replays it. A bare main seed starts from the first job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobSeed {
+    pub seed: u64,
+    /// Jobs started w/ `seed` before this one
+    pub job: u64,
+}
+
+impl JobSeed {
+    /// Seed of the job random generator.
+    pub fn derive(&self) -> u64 {
+        derive_seed(self.seed, SeedStream::Job, self.job)
+    }
+}
+
+impl Display for JobSeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.seed, self.job)
+    }
+}
+
+impl FromStr for JobSeed {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (seed, job) = match s.split_once('/') {
+            Some((seed, job)) => (seed.parse()?, job.parse()?),
+            None => (s.parse()?, 0),
+        };
+        Ok(JobSeed { seed, job })
+    }
+}
+
+/// SplitMix64 finalizer, close seeds give unrelated outputs.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Output type of a generated address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AddressType {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+    P2wsh,
+    P2tr,
+}
+
+impl AddressType {
+    pub const ALL: [AddressType; 5] = [
+        AddressType::P2pkh,
+        AddressType::P2shP2wpkh,
+        AddressType::P2wpkh,
+        AddressType::P2wsh,
+        AddressType::P2tr,
+    ];
+
+    /// `getnewaddress` type, a wallet can't generate single key P2WSH addresses.
+    pub fn wallet_type(&self) -> Option<json::AddressType> {
+        match self {
+            AddressType::P2pkh => Some(json::AddressType::Legacy),
+            AddressType::P2shP2wpkh => Some(json::AddressType::P2shSegwit),
+            AddressType::P2wpkh => Some(json::AddressType::Bech32),
+            AddressType::P2wsh => None,
+            AddressType::P2tr => Some(json::AddressType::Bech32m),
+        }
+    }
+}
+
+impl Display for AddressType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressType::P2pkh => write!(f, "P2PKH"),
+            AddressType::P2shP2wpkh => write!(f, "P2SH-P2WPKH"),
+            AddressType::P2wpkh => write!(f, "P2WPKH"),
+            AddressType::P2wsh => write!(f, "P2WSH"),
+            AddressType::P2tr => write!(f, "P2TR"),
+        }
+    }
+}
+
+/// Address types a new address is picked from (uniformly).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddressMix(pub BTreeSet<AddressType>);
+
+impl AddressMix {
+    /// Type of a random (burn) address, P2PKH if the mix is empty.
+    pub fn random_type<R: Rng>(&self, rng: &mut R) -> AddressType {
+        Self::pick(rng, self.0.iter().copied().collect()).unwrap_or(AddressType::P2pkh)
+    }
+
+    /// Type of a wallet receive or change address, `None` (the node default) if the mix
+    /// contains no wallet type.
+    pub fn wallet_type<R: Rng>(&self, rng: &mut R) -> Option<json::AddressType> {
+        Self::pick(rng, self.0.iter().filter_map(|t| t.wallet_type()).collect())
+    }
+
+    /// The generator is not drawn if there is no choice, so a run w/o mix replays the
+    /// same w/ the same seed.
+    fn pick<R: Rng, T>(rng: &mut R, mut types: Vec<T>) -> Option<T> {
+        match types.len() {
+            0 | 1 => types.pop(),
+            count => Some(types.swap_remove(rng.gen_range(0..count))),
+        }
+    }
+}
+
+impl Display for AddressMix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "default")
+        } else {
+            let types = self.0.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+            write!(f, "{}", types.join("/"))
+        }
+    }
+}
+
+/// Assert `mean`, the mean of `samples` draws of a distribution of mean `expected` and
+/// variance `variance`, is within 5 standard errors (`sqrt(variance / samples)`) of
+/// `expected`: a sound generator fails it about once in 1.7M seeds.
+#[cfg(test)]
+pub fn assert_mean(what: &str, mean: f64, expected: f64, variance: f64, samples: usize) {
+    let tolerance = 5.0 * (variance / samples as f64).sqrt();
+    assert!(
+        (mean - expected).abs() <= tolerance,
+        "{}: mean {} expected {} +/- {}",
+        what,
+        mean,
+        expected,
+        tolerance
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    const SAMPLES: usize = 100_000;
+
+    fn mean_variance(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, variance)
+    }
+
+    /// Check the mean & variance of `SAMPLES` tx counts match a Poisson distribution.
+    fn check_tx_count(count: u32, blocks: u32) {
+        let mut rng = StdRng::seed_from_u64(42);
+        let samples = (0..SAMPLES)
+            .map(|_| tx_count(&mut rng, count, blocks) as f64)
+            .collect::<Vec<_>>();
+        let (mean, variance) = mean_variance(&samples);
+        let expected = count as f64 / blocks.max(1) as f64;
+        let what = format!("{} tx every {} blocks", count, blocks);
+        // Poisson variance equals its mean
+        assert_mean(&what, mean, expected, expected, SAMPLES);
+        assert!(
+            (variance - expected).abs() <= 0.05 * expected,
+            "{} tx every {} blocks: variance {} expected {}",
+            count,
+            blocks,
+            variance,
+            expected
+        );
+    }
+
+    #[test]
+    fn tx_count_mean() {
+        // less than one tx per block
+        check_tx_count(1, 10);
+        check_tx_count(1, 2);
+        check_tx_count(3, 4);
+        // exactly one tx per block
+        check_tx_count(5, 5);
+        // more than one tx per block
+        check_tx_count(3, 2);
+        check_tx_count(10, 1);
+        // several Poisson chunks
+        check_tx_count(250, 1);
+    }
+
+    #[test]
+    fn tx_count_edge_cases() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..1_000 {
+            assert_eq!(tx_count(&mut rng, 0, 10), 0);
+            assert_eq!(tx_count(&mut rng, 0, 0), 0);
+        }
+        // 0 blocks is handled as 1 block
+        let samples = (0..SAMPLES)
+            .map(|_| tx_count(&mut rng, 2, 0) as f64)
+            .collect::<Vec<_>>();
+        let (mean, _) = mean_variance(&samples);
+        assert!((mean - 2.0).abs() < 0.05, "mean {}", mean);
+    }
+
+    #[test]
+    fn tx_count_is_reproducible() {
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        for _ in 0..1_000 {
+            assert_eq!(tx_count(&mut a, 3, 7), tx_count(&mut b, 3, 7));
+        }
+    }
+
+    #[test]
+    fn amount_bounds() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let (min, max) = (Amount::from_sat(1_000), Amount::from_sat(2_000));
+        for _ in 0..10_000 {
+            let amount = amount(&mut rng, min, max);
+            assert!(amount >= min && amount <= max);
+            let inverted = super::amount(&mut rng, max, min);
+            assert!(inverted >= min && inverted <= max);
+        }
+        // equal bounds
+        for _ in 0..1_000 {
+            assert_eq!(amount(&mut rng, min, min), min);
+        }
+        assert_eq!(amount(&mut rng, Amount::ZERO, Amount::ZERO), Amount::ZERO);
+    }
+
+    #[test]
+    fn amount_mean() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let (min, max) = (Amount::from_sat(10_000), Amount::from_sat(1_010_000));
+        let samples = (0..SAMPLES)
+            .map(|_| amount(&mut rng, min, max).to_sat() as f64)
+            .collect::<Vec<_>>();
+        let (mean, variance) = mean_variance(&samples);
+        let expected = (min.to_sat() + max.to_sat()) as f64 / 2.0;
+        // uniform variance is width^2 / 12
+        let expected_variance = ((max - min).to_sat() as f64).powi(2) / 12.0;
+        assert_mean("amount", mean, expected, expected_variance, SAMPLES);
+        assert!(
+            (variance - expected_variance).abs() <= 0.05 * expected_variance,
+            "variance {} expected {}",
+            variance,
+            expected_variance
+        );
+    }
+
+    #[test]
+    fn derived_seeds() {
+        let seeds = (0..1_000)
+            .flat_map(|id| {
+                [
+                    derive_seed(42, SeedStream::Job, id),
+                    derive_seed(42, SeedStream::AutoBlock, id),
+                    derive_seed(42, SeedStream::BlockInterval, id),
+                    derive_seed(43, SeedStream::Job, id),
+                ]
+            })
+            .collect::<BTreeSet<_>>();
+        assert_eq!(seeds.len(), 4_000);
+        assert_eq!(
+            derive_seed(42, SeedStream::Job, 7),
+            derive_seed(42, SeedStream::Job, 7)
+        );
+    }
+
+    #[test]
+    fn job_seed() {
+        let seed = JobSeed { seed: 42, job: 3 };
+        assert_eq!(seed.to_string(), "42/3");
+        assert_eq!(JobSeed::from_str("42/3"), Ok(seed));
+        assert_eq!(seed.derive(), derive_seed(42, SeedStream::Job, 3));
+        assert_eq!(JobSeed::from_str("42"), Ok(JobSeed { seed: 42, job: 0 }));
+        for invalid in ["", "42/", "/3", "42/3/1", "-1", "42/x"] {
+            assert!(JobSeed::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn address_mix() {
+        let mut rng = StdRng::seed_from_u64(42);
+        // no choice, the generator is not drawn
+        let mut reference = rng.clone();
+        let empty = AddressMix::default();
+        assert_eq!(empty.random_type(&mut rng), AddressType::P2pkh);
+        assert_eq!(empty.wallet_type(&mut rng), None);
+        let wsh = AddressMix([AddressType::P2wsh].into());
+        assert_eq!(wsh.random_type(&mut rng), AddressType::P2wsh);
+        assert_eq!(wsh.wallet_type(&mut rng), None);
+        assert_eq!(rng.gen::<u64>(), reference.gen::<u64>());
+
+        // every type of the mix is picked, uniformly
+        let mix = AddressMix(AddressType::ALL.into());
+        let mut counts = [0usize; 5];
+        for _ in 0..SAMPLES {
+            let picked = mix.random_type(&mut rng);
+            counts[AddressType::ALL.iter().position(|t| *t == picked).unwrap()] += 1;
+        }
+        let expected = SAMPLES as f64 / 5.0;
+        for count in counts {
+            assert!(
+                (count as f64 - expected).abs() < 0.05 * expected,
+                "{:?}",
+                counts
+            );
+        }
+        for _ in 0..1_000 {
+            assert!(mix.wallet_type(&mut rng).is_some());
+        }
+    }
+}